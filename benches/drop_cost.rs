@@ -0,0 +1,147 @@
+// What dropping costs at runtime, to put numbers on the drop glue lessons. Run with `cargo bench`.
+// Every routine gets a freshly built value from `iter_batched` and drops it, so only the drop is
+// timed, the allocations that built the value are not.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use std::{mem, ptr};
+
+const LEN: usize = 10_000;
+
+// Nothing to drop but a Drop impl of its own, which the optimizer can't remove
+struct Flagged(u64);
+
+impl Drop for Flagged {
+    fn drop(&mut self) {
+        black_box(self.0);
+    }
+}
+
+// The fields are never read, only dropped
+#[allow(dead_code)]
+struct Node {
+    value: u64,
+    next: Option<Box<Node>>,
+}
+
+// A list of Boxes in Boxes, dropped by the default glue one level of recursion per node
+fn deep(len: usize) -> Option<Box<Node>> {
+    (0..len as u64).fold(None, |next, value| Some(Box::new(Node { value, next })))
+}
+
+// Drops the elements through a function pointer, the way a container that erased their type has
+// to, e.g. an arena. `black_box` hides which function it is, so the calls can't be optimized away.
+fn drop_erased<T>(mut vec: Vec<T>, check: bool) -> Vec<T> {
+    if check && !mem::needs_drop::<T>() {
+        // SAFETY: T has no drop glue, forgetting the elements is all that dropping them would do
+        unsafe { vec.set_len(0) };
+        return vec;
+    }
+    let drop_fn = black_box(ptr::drop_in_place::<T> as unsafe fn(*mut T));
+    let len = vec.len();
+    // SAFETY: the length goes to 0 first, so every element is dropped exactly once
+    unsafe {
+        vec.set_len(0);
+        for i in 0..len {
+            drop_fn(vec.as_mut_ptr().add(i));
+        }
+    }
+    vec
+}
+
+// A Vec of a Copy type frees its buffer and that's all, the elements have no glue to run. Any
+// Drop impl brings a loop over every element back, and a String element a free each.
+fn copy_vs_drop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("copy_vs_drop");
+    group.bench_function("Vec<u64>", |b| {
+        b.iter_batched(|| vec![0u64; LEN], drop, BatchSize::SmallInput)
+    });
+    group.bench_function("Vec<Flagged>", |b| {
+        b.iter_batched(
+            || (0..LEN as u64).map(Flagged).collect::<Vec<_>>(),
+            drop,
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("Vec<String>", |b| {
+        b.iter_batched(|| vec![String::from("x"); LEN], drop, BatchSize::SmallInput)
+    });
+    group.finish();
+}
+
+// Dropping a Box is a free on top of dropping its content, so a Vec of Boxes pays one per element
+fn box_vs_inline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("box_vs_inline");
+    group.bench_function("Vec<[u64; 4]>", |b| {
+        b.iter_batched(|| vec![[0u64; 4]; LEN], drop, BatchSize::SmallInput)
+    });
+    group.bench_function("Vec<Box<[u64; 4]>>", |b| {
+        b.iter_batched(
+            || vec![Box::new([0u64; 4]); LEN],
+            drop,
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+// The same number of Boxes, chained or side by side. The chain is dropped by recursion and
+// chases a pointer at every step, the Vec walks its buffer.
+fn deep_vs_shallow(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deep_vs_shallow");
+    group.bench_function("list of Boxes", |b| {
+        b.iter_batched(|| deep(LEN), drop, BatchSize::SmallInput)
+    });
+    group.bench_function("Vec of Boxes", |b| {
+        b.iter_batched(
+            || (0..LEN as u64).map(Box::new).collect::<Vec<_>>(),
+            drop,
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+// Where the type is known, the glue of u64 is empty and a loop over it compiles to nothing. A
+// container that erased the type calls the drop function of every element all the same, unless it
+// checks `needs_drop` first, as Vec and the arenas do. For a String, the check costs nothing.
+fn needs_drop_fast_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("needs_drop_fast_path");
+    group.bench_function("call for every u64", |b| {
+        b.iter_batched(
+            || vec![0u64; LEN],
+            |vec| drop_erased(vec, false),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("skip the u64s", |b| {
+        b.iter_batched(
+            || vec![0u64; LEN],
+            |vec| drop_erased(vec, true),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("call for every String", |b| {
+        b.iter_batched(
+            || vec![String::from("x"); LEN],
+            |vec| drop_erased(vec, false),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("check, then call for every String", |b| {
+        b.iter_batched(
+            || vec![String::from("x"); LEN],
+            |vec| drop_erased(vec, true),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    copy_vs_drop,
+    box_vs_inline,
+    deep_vs_shallow,
+    needs_drop_fast_path
+);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+// The half of the temporaries5 lesson that has to be compiled as edition 2024. The edition is set
+// per crate, so it lives in a crate of its own. It cannot use the types of dm, which depends on it,
+// hence its own copy of DropSpy.
+
+struct DropSpy(&'static str);
+
+impl Drop for DropSpy {
+    fn drop(&mut self) {
+        println!("{} dropped", self.0);
+    }
+}
+
+// Since edition 2024, the temporaries of the scrutinee of `if let` are dropped before the `else`
+pub fn if_let() {
+    if let "never" = DropSpy("scrutinee in 2024").0 {
+    } else {
+        println!("in the else branch in 2024");
+    }
+}
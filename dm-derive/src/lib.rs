@@ -0,0 +1,71 @@
+// `#[derive(TraceDrop)]` gives your own struct or enum a Drop impl that records the drop in
+// `dm::tracker` and prints the type together with its fields in the order the drop glue is going
+// to drop them. Annotate a type and watch the glue fire without writing the Drop impl yourself:
+//
+// #[derive(TraceDrop)]
+// struct A(B1, B2);
+//
+// Dropping an A prints `A dropped, drop glue follows: 0, 1`, and then whatever B1 and B2 print.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+#[proc_macro_derive(TraceDrop)]
+pub fn derive_trace_drop(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Fields are dropped in declaration order. For an enum, only the fields of the live variant
+    // are, so the order can only be told at runtime.
+    let glue = match &input.data {
+        Data::Struct(data) => {
+            let fields = field_names(&data.fields);
+            quote!(#fields)
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                let fields = field_names(&variant.fields);
+                quote!(#name::#variant_name { .. } => #fields)
+            });
+            quote!(match self { #(#arms,)* })
+        }
+        Data::Union(_) => {
+            let message = "TraceDrop can't be derived for a union, its fields are never dropped";
+            return syn::Error::new_spanned(&input.ident, message)
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    quote! {
+        impl #impl_generics ::core::ops::Drop for #name #ty_generics #where_clause {
+            fn drop(&mut self) {
+                ::dm::tracker::dropped(stringify!(#name));
+                let glue: &str = #glue;
+                if glue.is_empty() {
+                    println!("{} dropped, no drop glue follows", stringify!(#name));
+                } else {
+                    println!("{} dropped, drop glue follows: {}", stringify!(#name), glue);
+                }
+            }
+        }
+    }
+    .into()
+}
+
+// The fields as a comma separated string literal: names for named fields, indices for tuple ones
+fn field_names(fields: &Fields) -> String {
+    let names: Vec<_> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len()).map(|i| i.to_string()).collect(),
+        Fields::Unit => Vec::new(),
+    };
+    names.join(", ")
+}
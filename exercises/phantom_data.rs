@@ -0,0 +1,70 @@
+// MyBox owns its T through a pointer, and its Drop has `#[may_dangle] T` so that a MyBox<&str>
+// may outlive the str, like a Box can. But a pointer to T doesn't tell the drop checker that
+// MyBox owns a T and drops it. So it lets a MyBox<PrintOnDrop> outlive the str borrowed by the
+// PrintOnDrop as well, whose Drop then reads the freed str: see `must_not_compile` below.
+//
+// Tell the drop checker that a MyBox owns a T, so that the code under `must_not_compile` is
+// refused while the tests still pass. See phantom3.
+//
+// `cargo run -- check phantom_data` runs the tests below, and compiles the code under
+// `must_not_compile`, which must fail. It needs a nightly toolchain.
+
+#![feature(dropck_eyepatch)]
+
+use std::{cell::Cell, ptr::NonNull};
+
+pub struct MyBox<T> {
+    ptr: NonNull<T>,
+}
+
+impl<T> MyBox<T> {
+    pub fn new(value: T) -> MyBox<T> {
+        MyBox {
+            ptr: NonNull::from(Box::leak(Box::new(value))),
+        }
+    }
+}
+
+unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+    fn drop(&mut self) {
+        // SAFETY: the pointer comes from Box::leak in new, and is only ever freed here
+        unsafe { drop(Box::from_raw(self.ptr.as_ptr())) };
+    }
+}
+
+pub struct PrintOnDrop<'a>(pub &'a str);
+
+impl Drop for PrintOnDrop<'_> {
+    fn drop(&mut self) {
+        println!("{} dropped", self.0);
+    }
+}
+
+#[cfg(must_not_compile)] // error[E0597]
+fn print_on_drop_outlives_its_str() {
+    let boxed;
+    let name = String::from("freed before the MyBox");
+    boxed = MyBox::new(PrintOnDrop(&name));
+}
+
+#[test]
+fn a_reference_may_still_dangle() {
+    let boxed;
+    let name = String::from("freed before the MyBox");
+    boxed = MyBox::new(name.as_str());
+    drop(boxed);
+}
+
+#[test]
+fn drops_what_it_owns() {
+    struct Counted<'a>(&'a Cell<usize>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    drop(MyBox::new(Counted(&dropped)));
+    assert_eq!(dropped.get(), 1);
+}
@@ -0,0 +1,50 @@
+// A Session holds a Connection and the Transaction open on it. A Transaction has to be rolled
+// back while its Connection is still open, so the Transaction must be dropped first. Session has
+// no Drop of its own, its fields are dropped by the drop glue.
+//
+// Fix the Session so that the test passes, without giving it a Drop impl. See drop_glue1.
+//
+// `cargo run -- check drop_order` runs the tests below.
+
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn log(line: &'static str) {
+    LOG.with(|log| log.borrow_mut().push(line));
+}
+
+pub struct Connection;
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        log("connection closed");
+    }
+}
+
+pub struct Transaction;
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        log("transaction rolled back");
+    }
+}
+
+// The fields are dropped in the order of their declaration, so the Transaction comes first
+pub struct Session {
+    pub transaction: Transaction,
+    pub connection: Connection,
+}
+
+#[test]
+fn the_transaction_is_rolled_back_before_the_connection_closes() {
+    let session = Session {
+        connection: Connection,
+        transaction: Transaction,
+    };
+    drop(session);
+    let log = LOG.with(|log| log.take());
+    assert_eq!(log, ["transaction rolled back", "connection closed"]);
+}
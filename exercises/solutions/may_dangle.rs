@@ -0,0 +1,59 @@
+// MyVec keeps its items in a Vec and counts them in its Drop, which never looks at an item. Yet a
+// MyVec<&str> can't outlive the str it points to, not even by a moment at the end of a scope:
+// the drop checker assumes that the Drop may use every T, and that a &str must be valid then.
+// Vec itself has no such problem.
+//
+// Promise the drop checker that the Drop of MyVec doesn't use its items, so that the test
+// compiles. See may_dangle4.
+//
+// `cargo run -- check may_dangle` runs the tests below. It needs a nightly toolchain.
+
+#![feature(dropck_eyepatch)]
+
+use std::cell::Cell;
+
+thread_local! {
+    static DROPPED: Cell<usize> = const { Cell::new(0) };
+}
+
+pub struct MyVec<T> {
+    items: Vec<T>,
+}
+
+impl<T> MyVec<T> {
+    pub fn new() -> MyVec<T> {
+        MyVec { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+// SAFETY: the Drop counts the items but never uses one
+unsafe impl<#[may_dangle] T> Drop for MyVec<T> {
+    fn drop(&mut self) {
+        DROPPED.with(|dropped| dropped.set(dropped.get() + self.items.len()));
+    }
+}
+
+#[test]
+fn may_hold_references_to_values_declared_after_it() {
+    let mut names = MyVec::new();
+    let name = String::from("declared after the MyVec");
+    names.push(name.as_str());
+    assert_eq!(names.len(), 1);
+}
+
+#[test]
+fn still_counts_its_items_when_dropped() {
+    let mut numbers = MyVec::new();
+    numbers.push(1);
+    numbers.push(2);
+    drop(numbers);
+    assert_eq!(DROPPED.with(Cell::get), 2);
+}
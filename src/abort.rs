@@ -0,0 +1,66 @@
+// Compare what an example prints when panics unwind with what it prints when they abort. The
+// example is compiled on its own twice, with `-C panic=unwind` and with `-C panic=abort`, and both
+// programs run. With abort, a panic ends the process right away: nothing is dropped, and
+// catch_unwind has nothing to catch. The lines that only the unwinding program printed are the
+// destructors that abort skips.
+
+use crate::scratch::Scratch;
+use dm::{Example, EXAMPLES};
+use similar::{ChangeTag, TextDiff};
+use std::{
+    env, fs, io,
+    process::{Command, ExitStatus},
+};
+
+// Build the example with the given panic strategy and run it. Returns what it printed to stdout,
+// which is where the examples tell what they drop, and how it ended.
+fn build_and_run(example: &Example, strategy: &str) -> io::Result<(String, ExitStatus)> {
+    let dir = Scratch::new(&format!("dm-abort-{}", example.name))?;
+    let (source, binary) = (dir.join("main.rs"), dir.join(strategy));
+    fs::write(&source, example.program("").map_err(io::Error::other)?)?;
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "-C"])
+        .arg(format!("panic={}", strategy))
+        .arg("-o")
+        .arg(&binary)
+        .arg(&source)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("rustc failed:\n{}", stderr)));
+    }
+    let output = Command::new(&binary).output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    Ok((stdout, output.status))
+}
+
+fn compare(example: &Example) -> io::Result<()> {
+    let (unwind, unwound) = build_and_run(example, "unwind")?;
+    let (abort, aborted) = build_and_run(example, "abort")?;
+    println!("===== {}: panic=unwind vs panic=abort =====", example.name);
+    // Every line of both, the way `solution --diff` compares an attempt with its solution: ' ' for
+    // a line both printed, '-' for one only `unwind` printed and '+' for one only `abort` printed
+    for change in TextDiff::from_lines(&unwind, &abort).iter_all_changes() {
+        let sign = match change.tag() {
+            ChangeTag::Equal => ' ',
+            ChangeTag::Delete => '-',
+            ChangeTag::Insert => '+',
+        };
+        println!("{} {}", sign, change.value().trim_end_matches('\n'));
+    }
+    println!("panic=unwind ended with {}", unwound);
+    println!("panic=abort ended with {}", aborted);
+    Ok(())
+}
+
+// Compare the given example, or all the examples about unwinding
+pub fn run(example: Option<&Example>) -> io::Result<()> {
+    match example {
+        Some(example) => compare(example),
+        None => EXAMPLES
+            .iter()
+            .filter(|example| example.topic == "unwind")
+            .try_for_each(compare),
+    }
+}
@@ -0,0 +1,89 @@
+use crate::tracker;
+use std::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+};
+
+// An arena allocates values that all live exactly as long as the arena, so they can borrow from
+// each other freely, even in cycles, which plain ownership can't express. `alloc` takes &self and
+// returns a &'a T that lives as long as the borrow of the arena. The values are kept in chunks
+// that never grow once allocated, so a value never moves and the references stay valid.
+//
+// The nodes here are Node<'a>, borrowing other nodes of the same arena for 'a, so the arena is an
+// Arena<Node<'a>> where 'a is a borrow of the arena itself. When the arena is dropped, that
+// borrow ends with it, and whatever is in there has dangling references. Two promises make it
+// fine, both with #[may_dangle]:
+// - the Drop of Arena doesn't touch a T other than to drop it, and the PhantomData<T> tells the
+//   drop checker that it does drop them;
+// - the Drop of Node doesn't follow its references, it only prints its name.
+// Without either of them, `arena` would have to strictly outlive itself: "`arena` does not live
+// long enough".
+#[allow(unused)]
+pub fn arena1() {
+    struct Arena<T> {
+        chunks: RefCell<Vec<Vec<T>>>,
+        _owns: PhantomData<T>,
+    }
+    impl<T> Arena<T> {
+        fn new() -> Arena<T> {
+            Arena {
+                chunks: RefCell::new(vec![Vec::with_capacity(2)]),
+                _owns: PhantomData,
+            }
+        }
+        fn alloc(&self, value: T) -> &T {
+            let mut chunks = self.chunks.borrow_mut();
+            let full = chunks
+                .last()
+                .is_some_and(|chunk| chunk.len() == chunk.capacity());
+            if full {
+                let capacity = 2 * chunks.last().map_or(1, Vec::capacity);
+                chunks.push(Vec::with_capacity(capacity));
+            }
+            let chunk = chunks.last_mut().unwrap();
+            chunk.push(value);
+            let value = chunk.last().unwrap() as *const T;
+            // Safety: the chunk never grows past its capacity, so the value never moves, and it
+            // is only dropped along with the arena, which this borrow can't outlive
+            unsafe { &*value }
+        }
+    }
+    unsafe impl<#[may_dangle] T> Drop for Arena<T> {
+        fn drop(&mut self) {
+            tracker::dropped("Arena");
+            let chunks = self.chunks.get_mut();
+            println!("Arena dropped with {} chunks", chunks.len());
+            // the chunks get dropped here, and the nodes in them
+        }
+    }
+
+    struct Node<'a> {
+        name: &'static str,
+        next: Cell<Option<&'a Node<'a>>>,
+    }
+    unsafe impl<#[may_dangle] 'a> Drop for Node<'a> {
+        fn drop(&mut self) {
+            tracker::dropped(self.name);
+            println!("{} dropped", self.name);
+        }
+    }
+
+    let arena = Arena::new();
+    let node = |name| {
+        arena.alloc(Node {
+            name,
+            next: Cell::new(None),
+        })
+    };
+    let (a, b, c) = (node("a"), node("b"), node("c"));
+    a.next.set(Some(b));
+    b.next.set(Some(c));
+    c.next.set(Some(a));
+    let mut walk = a;
+    for _ in 0..4 {
+        print!("{} -> ", walk.name);
+        walk = walk.next.get().unwrap();
+    }
+    println!("{}", walk.name);
+    // arena gets dropped here, with the cycle of references in it
+}
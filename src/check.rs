@@ -0,0 +1,155 @@
+// Exercises the way rustlings does them: every file in exercises/ is a small program that is broken
+// or unfinished on purpose, e.g. a MyBox without its PhantomData. Fix it in place, then `check`
+// tells whether it is done. DM_EXERCISES points to another directory of exercises.
+//
+// An exercise is done when its tests compile and pass, with `rustc --test`. Some fixes are about
+// code that must not compile, like the one PhantomData makes the drop checker refuse. That code
+// goes under `#[cfg(must_not_compile)]` in the exercise, with the error expected of it in a comment
+// after, e.g. `// error[E0597]`. It is compiled on its own with that cfg set: the exercise is only
+// done if the compiler refuses it with that error. Whether there is such code to refuse is up to
+// the solution, see solution.rs, so that deleting it from the attempt doesn't do.
+
+use crate::scratch::Scratch;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const MUST_NOT_COMPILE: &str = "#[cfg(must_not_compile)]";
+
+pub fn dir() -> PathBuf {
+    env::var_os("DM_EXERCISES").map_or_else(
+        || PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/exercises")),
+        PathBuf::from,
+    )
+}
+
+// The names of the exercises in `dir`, sorted
+pub fn names(dir: &Path) -> io::Result<Vec<String>> {
+    let mut names: Vec<_> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+// What the attempt at `path` is checked against: its solution next to it, or else the one shipped
+// with the crate, e.g. for DM_EXERCISES pointing to the solutions themselves
+fn reference(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default();
+    let shipped = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/exercises/solutions"));
+    let beside = path.with_file_name("solutions").join(name);
+    [beside, shipped.join(name)]
+        .into_iter()
+        .find(|solution| solution.exists())
+        .unwrap_or_else(|| path.to_owned())
+}
+
+// The error the code under MUST_NOT_COMPILE is to be refused with, e.g. `error[E0597]`, if there is
+// such code. Any error does without one.
+fn must_not_compile(source: &str) -> Option<Option<&str>> {
+    let line = source
+        .lines()
+        .find(|line| line.starts_with(MUST_NOT_COMPILE))?;
+    let error = line
+        .split_once("// ")
+        .map(|(_, error)| error.trim())
+        .filter(|error| !error.is_empty());
+    Some(error)
+}
+
+fn rustc() -> Command {
+    let mut rustc = Command::new(env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned()));
+    rustc.args(["--edition", "2021", "-A", "warnings"]);
+    rustc
+}
+
+// Ok if the exercise is done, or else what is still wrong with it
+pub fn check(path: &Path) -> io::Result<Result<(), String>> {
+    let scratch = Scratch::new("dm-check")?;
+    let name = path.file_stem().unwrap_or_default();
+    let tests = scratch.join(name);
+
+    let output = rustc()
+        .arg("--test")
+        .arg("-o")
+        .arg(&tests)
+        .arg(path)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Ok(Err(format!("it doesn't compile yet:\n\n{}", stderr)));
+    }
+    let output = Command::new(&tests).arg("-q").output()?;
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Ok(Err(format!("the tests fail:\n\n{}", stdout)));
+    }
+
+    let reference = fs::read_to_string(reference(path))?;
+    if let Some(expected) = must_not_compile(&reference) {
+        let output = rustc()
+            .args(["--crate-type", "lib", "--emit=metadata"])
+            .args(["--cfg", "must_not_compile", "-o"])
+            .arg(scratch.join(name).with_extension("rmeta"))
+            .arg(path)
+            .output()?;
+        if output.status.success() {
+            let reason = format!("the code under {} still compiles", MUST_NOT_COMPILE);
+            return Ok(Err(reason));
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if let Some(expected) = expected.filter(|expected| !stderr.contains(expected)) {
+            let reason = format!(
+                "the code under {} is refused, but not with {}:\n\n{}",
+                MUST_NOT_COMPILE, expected, stderr
+            );
+            return Ok(Err(reason));
+        }
+    }
+    Ok(Ok(()))
+}
+
+// Check the given exercise, or all of them, and tell what is wrong with the first one not done.
+// Returns whether they are all done.
+pub fn run(name: Option<&str>) -> io::Result<bool> {
+    let dir = dir();
+    let mut names = names(&dir)?;
+    if let Some(name) = name {
+        if !names.iter().any(|known| known == name) {
+            let message = format!(
+                "unknown exercise `{}`, valid names are: {}",
+                name,
+                names.join(", ")
+            );
+            return Err(io::Error::new(io::ErrorKind::NotFound, message));
+        }
+        names.retain(|known| known == name);
+    }
+
+    let mut first_failure = None;
+    let mut done = 0;
+    for name in &names {
+        let path = dir.join(format!("{}.rs", name));
+        match check(&path)? {
+            Ok(()) => {
+                done += 1;
+                println!("✓ {}", name);
+            }
+            Err(reason) => {
+                println!("✗ {}", name);
+                first_failure.get_or_insert((name, path, reason));
+            }
+        }
+    }
+    println!("{} of {} exercises done", done, names.len());
+    if let Some((name, path, reason)) = first_failure {
+        println!("\n{} is not done, {}", name, reason.trim_end());
+        println!("\nEdit {} and check again.", path.display());
+    }
+    Ok(done == names.len())
+}
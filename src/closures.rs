@@ -0,0 +1,63 @@
+use crate::util::DropSpy;
+
+// A `move` closure is a struct in disguise: the captured variables are its fields. So it owns
+// them, they are dropped when the closure is dropped and not where they were declared, and the
+// closure can be passed around like any other value, with its captures in tow. Mind that since
+// edition 2021 a closure captures only the fields it uses: one that only reads `captured.0` would
+// copy the name out and leave the DropSpy where it is.
+#[allow(unused)]
+pub fn closures1() {
+    let captured = DropSpy("captured");
+    let not_captured = DropSpy("not captured");
+    let closure = move || {
+        let spy = &captured;
+        println!("the closure can still see {}", spy.0);
+    };
+    closure();
+    closure();
+    drop(closure);
+    println!("the closure is gone, and what it captured with it");
+}
+
+// A closure that moves a capture out of itself, here into `drop`, can only be called once: it is
+// FnOnce, and calling it consumes the closure. The capture is dropped during the call, and there
+// is no closure left to drop afterwards. A FnOnce closure that is never called still drops its
+// captures when it goes out of scope, like any other value.
+#[allow(unused)]
+pub fn closures2() {
+    let consumed = DropSpy("consumed");
+    let once = move || {
+        println!("in the call");
+        drop(consumed);
+        println!("still in the call");
+    };
+    once();
+    // once(); // error[E0382]: use of moved value: `once`
+    println!("after the call");
+
+    let never_called = DropSpy("never called");
+    let unused = move || drop(never_called);
+    println!("the end of the block");
+}
+
+// A closure that is not `move` captures by reference, so it borrows the variables and must not
+// outlive them. Stored in a type with a Drop impl, the closure is used in `drop`, so the drop
+// checker requires the borrowed variables to strictly outlive the wrapper. Declared first, they
+// are dropped last, and all is well. A wrapper declared before the variable it borrows is not.
+#[allow(unused)]
+pub fn closures3() {
+    struct OnDrop<F: FnMut()>(F);
+    impl<F: FnMut()> Drop for OnDrop<F> {
+        fn drop(&mut self) {
+            (self.0)();
+        }
+    }
+
+    let target = DropSpy("target");
+    let guard = OnDrop(|| println!("the guard still sees {}", target.0));
+    // uncomment the following three lines to see the error (or read tests/ui/closures3.stderr)
+    // let early;
+    // let late = DropSpy("late");
+    // early = OnDrop(|| println!("the guard still sees {}", late.0));
+    println!("the end of the block");
+}
@@ -0,0 +1,179 @@
+// Tab completion for the shells, so that `dm dro<TAB>` completes the name of an example and
+// `dm solution <TAB>` the name of an exercise. The arguments are parsed by hand in main.rs, so the
+// scripts are written by hand as well, with every name baked in: print the one for your shell
+// again after adding an example. They complete the `dm` binary, e.g. from `cargo install --path .`
+// or target/debug on the PATH, since cargo can't be taught to complete what follows `cargo run --`.
+
+use crate::check;
+use dm::{locale, EXAMPLES};
+
+pub const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+// Keep in sync with the match in main.rs
+const COMMANDS: [&str; 17] = [
+    "list",
+    "run-all",
+    "check",
+    "solution",
+    "reset",
+    "quiz",
+    "walkthrough",
+    "explain",
+    "view",
+    "dot",
+    "mir",
+    "elaborate",
+    "miri",
+    "abort",
+    "timeline",
+    "tui",
+    "completions",
+];
+// The commands that take no example after them
+const NO_EXAMPLE: [&str; 4] = ["list", "run-all", "reset", "tui"];
+const DIFFICULTIES: [&str; 3] = ["intro", "intermediate", "advanced"];
+
+struct Words {
+    commands: String,
+    examples: String,
+    exercises: String,
+    topics: String,
+    languages: String,
+}
+
+fn words() -> Words {
+    let mut topics: Vec<_> = EXAMPLES.iter().map(|example| example.topic).collect();
+    topics.dedup();
+    let examples: Vec<_> = EXAMPLES.iter().map(|example| example.name).collect();
+    Words {
+        commands: COMMANDS.join(" "),
+        examples: examples.join(" "),
+        exercises: check::names(&check::dir()).unwrap_or_default().join(" "),
+        topics: topics.join(" "),
+        languages: locale::languages().join(" "),
+    }
+}
+
+fn bash(words: &Words) -> String {
+    format!(
+        r#"_dm() {{
+    local cur=${{COMP_WORDS[COMP_CWORD]}} prev=${{COMP_WORDS[COMP_CWORD-1]}} option candidates
+    # Bash splits `--topic=drop` at the `=`
+    if [ "$cur" = "=" ]; then
+        option=$prev
+        cur=
+    elif [ "$prev" = "=" ]; then
+        option=${{COMP_WORDS[COMP_CWORD-2]}}
+    fi
+    if [ -n "$option" ]; then
+        case $option in
+            --topic) candidates="{topics}" ;;
+            --difficulty) candidates="{difficulties}" ;;
+            --lang) candidates="{languages}" ;;
+            --trace) COMPREPLY=($(compgen -f -- "$cur")); return ;;
+        esac
+    elif [ "$COMP_CWORD" -eq 1 ]; then
+        candidates="{commands} {examples}"
+    else
+        case ${{COMP_WORDS[1]}} in
+            check) candidates="{exercises}" ;;
+            solution) candidates="{exercises} --diff" ;;
+            completions) candidates="{shells}" ;;
+            {no_example}) candidates="" ;;
+            *) candidates="{examples}" ;;
+        esac
+    fi
+    if [ -z "$option" ]; then
+        candidates="$candidates -q -v -vv --trace= --lang= --topic= --difficulty="
+    fi
+    COMPREPLY=($(compgen -W "$candidates" -- "$cur"))
+    [[ ${{COMPREPLY[0]}} == *= ]] && compopt -o nospace
+}}
+complete -F _dm dm
+"#,
+        commands = words.commands,
+        examples = words.examples,
+        exercises = words.exercises,
+        topics = words.topics,
+        languages = words.languages,
+        difficulties = DIFFICULTIES.join(" "),
+        shells = SHELLS.join(" "),
+        no_example = NO_EXAMPLE.join("|"),
+    )
+}
+
+fn zsh(words: &Words) -> String {
+    format!(
+        r#"#compdef dm
+_dm() {{
+    local -a candidates
+    case $PREFIX in
+        --topic=*) compset -P '*='; compadd -- {topics}; return ;;
+        --difficulty=*) compset -P '*='; compadd -- {difficulties}; return ;;
+        --lang=*) compset -P '*='; compadd -- {languages}; return ;;
+        --trace=*) compset -P '*='; _files; return ;;
+    esac
+    if (( CURRENT == 2 )); then
+        candidates=({commands} {examples})
+    else
+        case ${{words[2]}} in
+            check) candidates=({exercises}) ;;
+            solution) candidates=({exercises} --diff) ;;
+            completions) candidates=({shells}) ;;
+            {no_example}) candidates=() ;;
+            *) candidates=({examples}) ;;
+        esac
+    fi
+    compadd -- $candidates -q -v -vv
+    compadd -S '' -- --trace= --lang= --topic= --difficulty=
+}}
+compdef _dm dm
+"#,
+        commands = words.commands,
+        examples = words.examples,
+        exercises = words.exercises,
+        topics = words.topics,
+        languages = words.languages,
+        difficulties = DIFFICULTIES.join(" "),
+        shells = SHELLS.join(" "),
+        no_example = NO_EXAMPLE.join("|"),
+    )
+}
+
+fn fish(words: &Words) -> String {
+    let takes_no_example = format!("check solution completions {}", NO_EXAMPLE.join(" "));
+    format!(
+        r#"complete -c dm -f
+complete -c dm -n __fish_use_subcommand -a "{commands} {examples}"
+complete -c dm -n "__fish_seen_subcommand_from check solution" -a "{exercises}"
+complete -c dm -n "__fish_seen_subcommand_from solution" -l diff
+complete -c dm -n "__fish_seen_subcommand_from completions" -a "{shells}"
+complete -c dm -n "not __fish_use_subcommand; and not __fish_seen_subcommand_from {takes_no_example}" -a "{examples}"
+complete -c dm -s q -l quiet
+complete -c dm -s v -l verbose
+complete -c dm -l trace -r -F
+complete -c dm -l lang -x -a "{languages}"
+complete -c dm -l topic -x -a "{topics}"
+complete -c dm -l difficulty -x -a "{difficulties}"
+"#,
+        commands = words.commands,
+        examples = words.examples,
+        exercises = words.exercises,
+        topics = words.topics,
+        languages = words.languages,
+        difficulties = DIFFICULTIES.join(" "),
+        shells = SHELLS.join(" "),
+        takes_no_example = takes_no_example,
+    )
+}
+
+// The completion script for `shell`, one of SHELLS
+pub fn script(shell: &str) -> Option<String> {
+    let words = words();
+    match shell {
+        "bash" => Some(bash(&words)),
+        "zsh" => Some(zsh(&words)),
+        "fish" => Some(fish(&words)),
+        _ => None,
+    }
+}
@@ -0,0 +1,31 @@
+use crate::util::DropSpy;
+use std::ops::{Coroutine, CoroutineState};
+
+// Under an async fn there's a coroutine: a closure that can `yield` halfway and be resumed later
+// from where it stopped. Coroutines are unstable on their own, but they show plainly what the
+// compiler makes of them: a struct with a field for every local that is alive at a `yield`, and
+// a state telling which yield it stopped at. Dropping a suspended coroutine runs the drop glue of
+// that struct, for the locals of the state it is in and nothing else. Here it stops at the second
+// yield with a and b alive, c never comes to be.
+#[allow(unused)]
+pub fn coroutines1() {
+    let coroutine = #[coroutine]
+    || {
+        let a = DropSpy("a");
+        yield 1;
+        let b = DropSpy("b");
+        yield 2;
+        let c = DropSpy("c");
+        3
+    };
+
+    let mut coroutine = Box::pin(coroutine);
+    for _ in 0..2 {
+        match coroutine.as_mut().resume(()) {
+            CoroutineState::Yielded(n) => println!("yielded {}", n),
+            CoroutineState::Complete(n) => println!("returned {}", n),
+        }
+    }
+    drop(coroutine);
+    println!("the coroutine was dropped while suspended at the second yield");
+}
@@ -0,0 +1,91 @@
+use crate::tracker;
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+// `Rc` shares the ownership of a value: every clone is an owner, and the value is dropped when the
+// last one is gone. Two values that own each other through Rc are never the last one, so they are
+// never dropped. No Drop runs and the memory is never freed, which the summary after the example
+// reports, all in safe Rust. This is why leaking is not considered unsafe.
+#[allow(unused)]
+pub fn cycles1() {
+    struct Node {
+        name: &'static str,
+        next: Option<Rc<RefCell<Node>>>,
+    }
+    impl Drop for Node {
+        fn drop(&mut self) {
+            tracker::dropped(self.name);
+            println!("{} dropped, but you will never see this line", self.name);
+        }
+    }
+
+    let a = Rc::new(RefCell::new(Node {
+        name: "a",
+        next: None,
+    }));
+    let b = Rc::new(RefCell::new(Node {
+        name: "b",
+        next: Some(Rc::clone(&a)),
+    }));
+    a.borrow_mut().next = Some(Rc::clone(&b));
+    println!(
+        "a has {} owners, b has {} owners",
+        Rc::strong_count(&a),
+        Rc::strong_count(&b)
+    );
+    // b gets dropped here, but a.next still owns the node of b
+    // a gets dropped here, but the node of b still owns the node of a
+}
+
+// The way out is to own in one direction only. `Weak` points to a value shared by Rc without owning
+// it: it doesn't keep the value alive, and `upgrade` returns None once the value is dropped. Let
+// the parent own its children and the children only refer to their parent with Weak.
+//
+// Rc counts the strong owners and the Weak references separately. When the strong count reaches
+// zero the value is dropped, but its memory is only freed when the weak count reaches zero too,
+// because the Weak still need somewhere to find out that the value is gone.
+#[allow(unused)]
+pub fn cycles2() {
+    struct Node {
+        name: &'static str,
+        parent: RefCell<Weak<Node>>,
+        children: RefCell<Vec<Rc<Node>>>,
+    }
+    impl Drop for Node {
+        fn drop(&mut self) {
+            tracker::dropped(self.name);
+            match self.parent.borrow().upgrade() {
+                Some(parent) => println!("{} dropped, {} is still there", self.name, parent.name),
+                None => println!("{} dropped, upgrading its parent gives None", self.name),
+            }
+        }
+    }
+
+    let parent = Rc::new(Node {
+        name: "parent",
+        parent: RefCell::new(Weak::new()),
+        children: RefCell::new(Vec::new()),
+    });
+    let child = Rc::new(Node {
+        name: "child",
+        parent: RefCell::new(Rc::downgrade(&parent)),
+        children: RefCell::new(Vec::new()),
+    });
+    parent.children.borrow_mut().push(Rc::clone(&child));
+    println!(
+        "parent: {} strong, {} weak",
+        Rc::strong_count(&parent),
+        Rc::weak_count(&parent)
+    );
+    println!(
+        "child: {} strong, {} weak",
+        Rc::strong_count(&child),
+        Rc::weak_count(&child)
+    );
+    // child gets dropped here: 1 strong left, owned by parent
+    // parent gets dropped here: 0 strong, so the parent is dropped, and its drop glue drops the
+    // child: 0 strong too. The child cannot upgrade to its parent anymore, although the memory of
+    // the parent is still there. It is freed when the Weak in the child goes, last of all.
+}
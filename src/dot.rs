@@ -0,0 +1,181 @@
+// Draw the types declared in an example as a Graphviz graph: who owns which field, who only
+// borrows or points to it, and the order in which the drop glue of each outermost type visits the
+// types it owns. Pipe it into Graphviz, e.g. `cargo run -- dot drop_glue3 | dot -Tsvg > glue.svg`.
+//
+// The graph is read off the source of the example, so it only knows what the struct declarations
+// say. That's all the drop glue knows, too.
+
+use dm::Example;
+use std::fmt::Write;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Edge {
+    Owns,
+    Borrows,
+    PointsTo,
+    // PhantomData<T>: owns T in the eyes of the drop checker, but nothing is dropped
+    Phantom,
+    // ManuallyDrop<T>: owns T, but the drop glue leaves it alone
+    ManuallyDrop,
+}
+
+struct Field {
+    name: String,
+    target: String,
+    edge: Edge,
+}
+
+// Split at the commas that are not nested in <>, () or []
+fn split_top_level(list: &str) -> Vec<&str> {
+    let (mut parts, mut depth, mut start) = (Vec::new(), 0, 0);
+    for (i, c) in list.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(list[start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+// The first identifier of a type, after references, pointers and lifetimes
+fn base(ty: &str) -> String {
+    ty.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '\''))
+        .find(|word| !word.is_empty() && !word.starts_with('\'') && *word != "mut")
+        .and_then(|word| (word != "const").then_some(word))
+        .unwrap_or(ty)
+        .to_owned()
+}
+
+fn field(name: String, ty: &str) -> Field {
+    let ty = ty.trim().trim_start_matches("pub ").trim();
+    let (edge, target) = if ty.starts_with('&') {
+        (Edge::Borrows, base(ty))
+    } else if let Some(pointee) = ty.strip_prefix("*mut").or(ty.strip_prefix("*const")) {
+        (Edge::PointsTo, base(pointee))
+    } else if let Some(phantom) = ty.strip_prefix("PhantomData<") {
+        (Edge::Phantom, base(phantom))
+    } else if let Some(inner) = ty.strip_prefix("ManuallyDrop<") {
+        (Edge::ManuallyDrop, base(inner))
+    } else {
+        (Edge::Owns, base(ty))
+    };
+    Field { name, target, edge }
+}
+
+// The structs declared in the code with their fields, in declaration order
+fn structs(code: &str) -> Vec<(String, Vec<Field>)> {
+    let mut structs = Vec::new();
+    let mut lines = code.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let Some(declaration) = line
+            .strip_prefix("struct ")
+            .or(line.strip_prefix("pub struct "))
+        else {
+            continue;
+        };
+        let name_end = declaration
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(declaration.len());
+        let name = declaration[..name_end].to_owned();
+        let rest = &declaration[name_end..];
+        let fields = if let (Some(open), Some(close)) = (rest.find('('), rest.rfind(')')) {
+            let list = split_top_level(&rest[open + 1..close]);
+            list.iter()
+                .enumerate()
+                .map(|(i, ty)| field(i.to_string(), ty))
+                .collect()
+        } else if rest.contains('{') {
+            // Named fields, on the same line or on the following ones up to the closing brace
+            let mut body = rest[rest.find('{').unwrap() + 1..].to_owned();
+            while !body.contains('}') {
+                match lines.next() {
+                    Some(line) => body.push_str(line),
+                    None => break,
+                }
+            }
+            let body = body.split('}').next().unwrap_or("");
+            split_top_level(body)
+                .iter()
+                .filter_map(|named| named.split_once(':'))
+                .map(|(name, ty)| field(name.trim().trim_start_matches("pub ").to_owned(), ty))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        structs.push((name, fields));
+    }
+    structs
+}
+
+// Number the types in the order the drop glue of `name` visits them
+fn visit<'a>(name: &'a str, structs: &'a [(String, Vec<Field>)], order: &mut Vec<&'a str>) {
+    order.push(name);
+    let Some((_, fields)) = structs.iter().find(|(declared, _)| declared == name) else {
+        return;
+    };
+    for field in fields.iter().filter(|field| field.edge == Edge::Owns) {
+        visit(&field.target, structs, order);
+    }
+}
+
+pub fn render(example: &Example) -> String {
+    let (_, code) = example.lesson();
+    let structs = structs(&code);
+    let mut dot = format!("digraph {} {{\n    node [shape=box];\n", example.name);
+
+    // Only the types nobody else owns are dropped on their own. Everything below them is dropped by
+    // their drop glue.
+    let roots = structs.iter().filter(|(name, _)| {
+        let owned = |(_, fields): &(String, Vec<Field>)| {
+            fields.iter().any(|field| {
+                matches!(field.edge, Edge::Owns | Edge::ManuallyDrop) && field.target == *name
+            })
+        };
+        !structs.iter().any(owned)
+    });
+    for (root, _) in roots {
+        let mut order = Vec::new();
+        visit(root, &structs, &mut order);
+        let steps: Vec<_> = order
+            .iter()
+            .enumerate()
+            .map(|(i, name)| format!("{}. {}", i + 1, name))
+            .collect();
+        let _ = writeln!(
+            dot,
+            "    \"{}\" [label=\"{}\\ndrop glue: {}\"];",
+            root,
+            root,
+            steps.join(", ")
+        );
+    }
+
+    for (name, fields) in &structs {
+        if fields.is_empty() {
+            let _ = writeln!(dot, "    \"{}\";", name);
+        }
+        for field in fields {
+            let (label, style) = match field.edge {
+                Edge::Owns => ("owns", "solid"),
+                Edge::Borrows => ("borrows", "dashed"),
+                Edge::PointsTo => ("points to", "dotted"),
+                Edge::Phantom => ("owns (PhantomData)", "dashed"),
+                Edge::ManuallyDrop => ("owns (ManuallyDrop)", "bold"),
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [label=\"{} .{}\", style={}];",
+                name, field.target, label, field.name, style
+            );
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
@@ -0,0 +1,73 @@
+use crate::util::DropSpy;
+
+// A variable declared without a value may be initialized on one branch only. Whether it has to be
+// dropped at the end of its scope is then only known at run time, so the compiler keeps a hidden
+// boolean next to it, a drop flag: set when the variable is assigned, checked at the end of the
+// scope. `cargo run -- mir drop_flags1` shows the drop of `maybe` behind its flag:
+//
+//   bb17  drop _12 (maybe): DropSpy  [if drop flag _29 is set]
+//
+// A variable that is always initialized, or never, doesn't need a flag: the compiler knows.
+#[allow(unused)]
+pub fn drop_flags1() {
+    for init in [true, false] {
+        let maybe;
+        if init {
+            maybe = DropSpy("maybe");
+        }
+        println!("the end of the iteration with init = {}", init);
+    }
+}
+
+// The same goes for a variable that is moved out on one branch only. The flag is cleared by the
+// move, so the value is dropped exactly once: by whoever took it, or at the end of the scope.
+#[allow(unused)]
+pub fn drop_flags2() {
+    fn take(spy: DropSpy) {
+        println!("took {}", spy.0);
+    }
+
+    for give in [true, false] {
+        let spy = DropSpy(if give { "given" } else { "kept" });
+        if give {
+            take(spy);
+        }
+        println!("the end of the iteration with give = {}", give);
+    }
+}
+
+// A struct without a Drop impl of its own can be taken apart: moving a field out leaves the other
+// fields in place, and only those are dropped at the end of the scope. The compiler tracks every
+// field on its own, with a drop flag per field when it has to. A struct that implements Drop
+// cannot lose a field, since its `drop` expects all of them to be there.
+#[allow(unused)]
+pub fn drop_flags3() {
+    struct Pair {
+        left: DropSpy,
+        right: DropSpy,
+    }
+
+    struct Guarded {
+        inner: DropSpy,
+    }
+    impl Drop for Guarded {
+        fn drop(&mut self) {
+            println!("Guarded still has {}", self.inner.0);
+        }
+    }
+
+    let pair = Pair {
+        left: DropSpy("left"),
+        right: DropSpy("right"),
+    };
+    let left = pair.left;
+    println!("moved {} out of the pair", left.0);
+    drop(left);
+    println!("only the right half is left in the pair");
+
+    let guarded = Guarded {
+        inner: DropSpy("inner"),
+    };
+    // uncomment the following line to see the error (or read tests/ui/drop_flags3.stderr)
+    // let inner = guarded.inner;
+}
@@ -0,0 +1,354 @@
+use crate::{tracker, util::DropSpy};
+
+// The destructor in rust consists of two parts to help us automatically drop all the resources
+// owned by the object:
+// - the programmer customized function `Drop::drop`
+// - Drop glue that the compiler automatically attached for us
+// Run the following example to see how it works
+
+#[allow(unused)]
+pub fn drop_glue1() {
+    struct A(B1, B2);
+    struct B1();
+    struct B2();
+    impl Drop for A {
+        // Even if you don't have a drop implementation, drop glue still applies to release the
+        // resources of its members. But here we have an explicit implementation though.
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("Drop for A called");
+            // It is like the compiler automatically attaches sub-drop routine
+            // at the end of the drop using "glue".
+            println!("The following is the drop glue of A");
+        }
+    }
+    impl Drop for B1 {
+        fn drop(&mut self) {
+            tracker::dropped("B1");
+            println!("Drop for B1 called as part of the drop glue of A");
+            println!("No drop glue for B1 since it has no field");
+        }
+    }
+    impl Drop for B2 {
+        fn drop(&mut self) {
+            tracker::dropped("B2");
+            println!("Drop for B2 called as part of the drop glue of A");
+            println!("No drop glue for B2 since it has no field");
+        }
+    }
+
+    A(B1(), B2());
+}
+
+// A drop glue only sticks OWNED members. If a member is a reference, the resources of it should be
+// managed by its owner instead of the borrower. In simple words, who owns it drops it.
+#[allow(unused)]
+pub fn drop_glue2() {
+    struct A<'a>(&'a B1, B2);
+    struct B1();
+    struct B2();
+    impl<'a> Drop for A<'a> {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("Drop for A called");
+        }
+    }
+    impl Drop for B1 {
+        fn drop(&mut self) {
+            tracker::dropped("B1");
+            println!("Drop for B1 called NOT as part of the drop glue of A");
+            println!("Instead, this is called because its owner b1 is dropped");
+        }
+    }
+    impl Drop for B2 {
+        // I'm called as part of drop glue of A.
+        fn drop(&mut self) {
+            tracker::dropped("B2");
+            println!("Drop for B2 called as part of the drop glue of A");
+        }
+    }
+
+    let b1 = B1();
+    A(&b1, B2());
+}
+
+// The drop glue can process recursively if the owned member also owns a member
+#[allow(unused)]
+pub fn drop_glue3() {
+    struct A(B1);
+    struct B1(C1, C2);
+    struct C1();
+    struct C2();
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("Drop for A called");
+        }
+    }
+    impl Drop for B1 {
+        fn drop(&mut self) {
+            tracker::dropped("B1");
+            println!("Drop for B1 as part of the drop glue of A");
+            println!("because the ownership of b1 is transferred to A")
+        }
+    }
+    impl Drop for C1 {
+        fn drop(&mut self) {
+            tracker::dropped("C1");
+            println!("Drop for C1 as part of the drop glue of B1");
+        }
+    }
+    impl Drop for C2 {
+        fn drop(&mut self) {
+            tracker::dropped("C2");
+            println!("Drop for C2 as part of the drop glue of B1");
+        }
+    }
+
+    let b1 = B1(C1(), C2());
+    A(b1);
+}
+
+// A trait object hides the concrete type, but not how to drop it. Next to the methods, every vtable
+// has a slot for the drop glue of the concrete type, together with its size and alignment. So
+// dropping a Box<dyn Shape> runs the Drop and the drop glue of A through that slot, then frees
+// the memory. A &dyn Shape is a reference like any other: it owns nothing and drops nothing.
+#[allow(unused)]
+pub fn drop_glue4() {
+    trait Shape {
+        fn name(&self) -> &'static str;
+    }
+    struct A(B);
+    struct B();
+    struct C();
+    impl Shape for A {
+        fn name(&self) -> &'static str {
+            "A"
+        }
+    }
+    impl Shape for C {
+        fn name(&self) -> &'static str {
+            "C"
+        }
+    }
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("Drop for A called through the vtable of dyn Shape");
+        }
+    }
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("Drop for B as part of the drop glue of A");
+        }
+    }
+    impl Drop for C {
+        fn drop(&mut self) {
+            tracker::dropped("C");
+            println!("Drop for C called because its owner c is dropped, not the &dyn Shape");
+        }
+    }
+
+    let c = C();
+    let borrowed: &dyn Shape = &c;
+    let boxed: Box<dyn Shape> = Box::new(A(B()));
+    println!(
+        "{} and {} are behind dyn Shape",
+        boxed.name(),
+        borrowed.name()
+    );
+    drop(boxed);
+    // borrowed gets dropped here, which does nothing
+    // c gets dropped here
+}
+
+// `std::mem::needs_drop::<T>()` tells whether dropping a T does anything at all, that is whether
+// T has a Drop impl or owns something that has one. When it doesn't, dropping is a no-op, e.g. a
+// reference or a struct of them like A in may_dangle1. Collections use it to skip the loop over
+// their elements. Drop check asks a different question: whether some Drop impl could look at a
+// borrow. A struct without Drop that owns a B needs drop, yet a reference in it may dangle.
+#[allow(unused)]
+pub fn drop_glue5() {
+    use std::{
+        marker::PhantomData,
+        mem::{needs_drop, ManuallyDrop},
+    };
+    struct A<'a>(&'a B);
+    struct B(i32);
+    struct C(i32);
+    struct D<'a>(B, &'a C);
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+        }
+    }
+
+    println!("i32: {}", needs_drop::<i32>());
+    println!("C without Drop: {}", needs_drop::<C>());
+    println!("B with Drop: {}", needs_drop::<B>());
+    println!("&B: {}", needs_drop::<&B>());
+    println!("A holding a &B: {}", needs_drop::<A>());
+    println!("D owning a B: {}", needs_drop::<D>());
+    println!("PhantomData<B>: {}", needs_drop::<PhantomData<B>>());
+    println!("ManuallyDrop<B>: {}", needs_drop::<ManuallyDrop<B>>());
+    println!("Option<B>: {}", needs_drop::<Option<B>>());
+    println!("Box<i32>: {}", needs_drop::<Box<i32>>());
+    println!("[B; 0]: {}", needs_drop::<[B; 0]>());
+}
+
+// A Copy type is duplicated bit by bit, so after `let b = a;` both a and b are usable and nothing
+// was moved. That's only sound because dropping a Copy type does nothing: with two copies of a
+// value that owns something, both would free it. So a Copy type has no drop glue, and can't have
+// a field that needs it. Wrapping one in a newtype with a Drop brings the glue back, and with it
+// moves instead of copies: Tracked can't be Copy, and each Tracked is dropped exactly once.
+//
+// The compiler says so with E0184 when a type is both. Were it allowed, `let d = c;` would leave
+// two Tracked behind, and the Drop would run twice on what is one value as far as its author is
+// concerned, e.g. to free the same pointer twice. Clone is fine: a clone is a new value made by
+// code that can duplicate what it owns, and it gets its own drop.
+#[allow(unused)]
+pub fn drop_glue6() {
+    use std::mem::needs_drop;
+    #[derive(Clone, Copy)]
+    struct Point(i32, i32);
+    // uncomment the following line to see the error (or read tests/ui/drop_glue6.stderr)
+    // #[derive(Clone, Copy)]
+    struct Tracked(Point);
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            tracker::dropped("Tracked");
+            println!("Tracked({}, {}) dropped", self.0 .0, self.0 .1);
+        }
+    }
+
+    let a = Point(1, 2);
+    let mut b = a;
+    b.0 = 10;
+    println!("a is still ({}, {}), b is ({}, {})", a.0, a.1, b.0, b.1);
+    println!("Point needs drop: {}", needs_drop::<Point>());
+    println!("Tracked needs drop: {}", needs_drop::<Tracked>());
+    let c = Tracked(a);
+    let d = c;
+    // a and b are copies, gone without a trace
+    // d gets dropped here, c was moved into it
+}
+
+// A zero-sized type takes no memory at all, but dropping it still runs its Drop: drop is about
+// the value, not about where it is stored. A Box of a ZST allocates nothing and still drops its
+// Token. A Vec of them never allocates either, it has room for usize::MAX of them from the start,
+// and only keeps a count. Dropping the Vec drops that many Tokens, one by one, out of nowhere.
+// MyVec in src/myvec.rs does the same.
+#[allow(unused)]
+pub fn drop_glue7() {
+    struct Token;
+    impl Drop for Token {
+        fn drop(&mut self) {
+            tracker::dropped("Token");
+            println!("Token dropped");
+        }
+    }
+
+    println!("a Token takes {} bytes", std::mem::size_of::<Token>());
+    let boxed = Box::new(Token);
+    let mut tokens = Vec::new();
+    for _ in 0..3 {
+        tokens.push(Token);
+    }
+    println!(
+        "the Vec has room for them all: {}",
+        tokens.capacity() == usize::MAX
+    );
+    drop(tokens.pop());
+    println!("{} Tokens left in the Vec", tokens.len());
+    // tokens gets dropped here, and the two Tokens in it
+    // boxed gets dropped here, and its Token with it
+}
+
+// The drop glue of an enum first looks at the discriminant, then drops the fields of the variant
+// that is there, and only those: the other variants don't exist in this value. A match can move
+// fields out of a variant, like a partial move out of a struct in drop_flags3. What was moved is
+// dropped by its new owner, and the rest stays in the enum until the enum is dropped. That only
+// works because Message has no Drop of its own, which would need the whole value.
+#[allow(unused)]
+pub fn drop_glue8() {
+    enum Message {
+        Text(DropSpy),
+        Pair(DropSpy, DropSpy),
+        Empty,
+    }
+
+    let text = Message::Text(DropSpy("text"));
+    let pair = Message::Pair(DropSpy("left"), DropSpy("right"));
+    let empty = Message::Empty;
+    match pair {
+        Message::Text(text) => println!("moved {} out of the text", text.0),
+        Message::Pair(left, _) => println!("moved {} out of the pair", left.0),
+        Message::Empty => {}
+    }
+    println!("the end of the block");
+    // empty gets dropped here, with nothing to drop in its variant
+    // pair gets dropped here, and only right is left in it
+    // text gets dropped here, with the DropSpy of its variant
+}
+
+// A Box<dyn Any> forgets the type of its value, but not how to drop it: that's in the vtable, as
+// for any trait object in drop_glue4. `downcast` gets the type back when it is the right one, and
+// the value is then a plain Box<Letter>, dropped through Letter's drop glue directly. When it is
+// not, the box comes back as it was and is dropped through the vtable. Either way the Drop of the
+// concrete type runs, once, and an i32 has none to run.
+#[allow(unused)]
+pub fn drop_glue9() {
+    use std::any::Any;
+    struct Letter;
+    struct Parcel;
+    impl Drop for Letter {
+        fn drop(&mut self) {
+            tracker::dropped("Letter");
+            println!("Letter dropped");
+        }
+    }
+    impl Drop for Parcel {
+        fn drop(&mut self) {
+            tracker::dropped("Parcel");
+            println!("Parcel dropped");
+        }
+    }
+
+    let items: Vec<Box<dyn Any>> = vec![Box::new(Letter), Box::new(Parcel), Box::new(7)];
+    for item in items {
+        // letter and other get dropped at the end of their arm
+        match item.downcast::<Letter>() {
+            Ok(letter) => println!("a Box<Letter> again"),
+            Err(other) => println!("still a Box<dyn Any>"),
+        }
+    }
+}
+
+// `T: Drop` reads like "a T that gets dropped", but it means "a T whose own type has a Drop impl".
+// String has none: its Vec<u8> does, and the drop glue of String calls it. So String doesn't meet
+// the bound, though dropping it frees memory, while a Vec<i32> does. The bound says nothing useful
+// about a type, which is why rustc warns about it with `drop_bounds`. Every type can be dropped,
+// so a function that only drops its T needs no bound at all, and one that wants to know whether
+// dropping does anything asks needs_drop, like in drop_glue5.
+#[allow(unused, drop_bounds)]
+pub fn drop_glue10() {
+    use std::mem::needs_drop;
+
+    fn dispose<T: Drop>(value: T) {
+        println!("disposing of a value whose type implements Drop");
+    }
+    fn dispose_any<T>(value: T) {
+        println!("disposing of anything, needs drop: {}", needs_drop::<T>());
+    }
+
+    dispose(DropSpy("spy"));
+    dispose(vec![1]);
+    // uncomment the following line to see the error (or read tests/ui/drop_glue10.stderr)
+    // dispose(String::from("text"));
+    dispose_any(String::from("text"));
+    dispose_any(7);
+}
+
+// For more details about how drop glue works, check [the standard
+// library](https://doc.rust-lang.org/std/ops/trait.Drop.html#drop-check)
@@ -0,0 +1,58 @@
+use crate::tracker;
+use crate::util::DropSpy;
+
+// drop order: declared first, drop last
+#[allow(unused)]
+pub fn drop_order() {
+    struct A();
+    struct B();
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A is dropped last because its declaration is the first")
+        }
+    }
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B is dropped first although its initialization is earlier than A");
+        }
+    }
+    let a;
+    let b = B();
+    a = A();
+}
+
+// The parameters of a function are its first locals, so they are dropped last when it returns,
+// after its other locals and in reverse order: b before a. Unless they are moved out, like the
+// returned value, which goes to the caller. There it is a temporary like any other: dropped at the
+// end of the statement, unless it is bound to a variable.
+#[allow(unused)]
+pub fn drop_order2() {
+    fn call(a: DropSpy, b: DropSpy) -> DropSpy {
+        let c = DropSpy("local c");
+        println!("in the call");
+        DropSpy("returned")
+    }
+
+    let name = call(DropSpy("a"), DropSpy("b")).0;
+    println!("back from the call that returned {}", name);
+    let kept = call(DropSpy("a again"), DropSpy("b again"));
+    println!(
+        "back from the call that returned {}, kept this time",
+        kept.0
+    );
+}
+
+// Variables are dropped in reverse order, but what is inside a value is not: the elements of a
+// tuple and of an array are dropped front to back, in index order, like the fields of a struct in
+// drop_glue1. A Vec does the same with its elements before freeing its buffer, and so do the
+// other collections of std that keep an order, e.g. VecDeque.
+#[allow(unused, clippy::useless_vec)]
+pub fn drop_order3() {
+    let vec = vec![DropSpy("vec[0]"), DropSpy("vec[1]"), DropSpy("vec[2]")];
+    let array = [DropSpy("array[0]"), DropSpy("array[1]")];
+    let tuple = (DropSpy("tuple.0"), DropSpy("tuple.1"));
+    println!("the end of the block");
+    // tuple gets dropped here, then array, then vec, each from its first element to its last
+}
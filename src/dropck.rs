@@ -0,0 +1,61 @@
+use crate::tracker;
+
+// The rule behind the errors so far, as the compiler applies it when a value goes out of scope:
+// - if its type has a Drop impl, every generic parameter of that type, lifetimes and types alike,
+//   must strictly outlive the value, that is still be alive when the value is dropped;
+// - if not, the rule applies to its fields instead, one by one, down to types with a Drop impl or
+//   without anything to drop at all, like a reference.
+// It only looks at the types, never at what the Drop actually does: an empty Drop counts as much
+// as one that reads everything. #[may_dangle] is the only way to exempt a parameter.
+//
+// Holder<&String> has a Drop impl, so &String must outlive it: fine for early, but a Holder
+// declared before late would be dropped after it, which is an error. Its "borrow might be used
+// here, when `too_early` is dropped and runs the `Drop` code for type `Holder`" is the first half
+// of the rule speaking: might, since the compiler doesn't look into the Drop. Plain<&String> has no
+// Drop impl, so the rule goes down to its field, a &String, which has nothing to drop. So a Plain
+// may hold a reference that is about to dangle.
+#[allow(unused, clippy::needless_late_init)]
+pub fn dropck1() {
+    struct Holder<T>(T);
+    impl<T> Drop for Holder<T> {
+        fn drop(&mut self) {
+            tracker::dropped("Holder");
+            println!("Holder dropped without looking at its T");
+        }
+    }
+    struct Plain<T>(T);
+
+    let early = String::from("early");
+    let holder = Holder(&early);
+    // uncomment the two lines below to see the error (or read tests/ui/dropck1.stderr)
+    // let too_early;
+    let plain;
+    let late = String::from("late");
+    plain = Plain(&late);
+    // too_early = Holder(&late);
+    println!("the end of the block");
+}
+
+// A closure is a type parameter like any other, and its type contains the lifetimes of what it
+// borrows. So a Holder of a closure that borrows late falls under the same rule, whether or not
+// the Drop ever calls the closure: late must outlive the Holder. A `move` closure that owns a
+// clone of the String borrows nothing, and the rule has no lifetime to check.
+#[allow(unused, clippy::needless_late_init)]
+pub fn dropck2() {
+    struct Holder<T>(T);
+    impl<T> Drop for Holder<T> {
+        fn drop(&mut self) {
+            tracker::dropped("Holder");
+            println!("Holder dropped without calling its closure");
+        }
+    }
+
+    // uncomment the two lines below to see the error (or read tests/ui/dropck2.stderr)
+    // let borrowing;
+    let owning;
+    let late = String::from("late");
+    let copy = late.clone();
+    owning = Holder(move || copy.len());
+    // borrowing = Holder(|| late.len());
+    println!("the end of the block");
+}
@@ -0,0 +1,49 @@
+// Print the annotation of an example in the terminal, so that the explanation can be read without
+// opening the source. The prose is rewrapped to the width of the terminal, as far as COLUMNS tells
+// it, and followed by the code it explains.
+
+use dm::Example;
+
+const WIDTH: usize = 80;
+
+// Fill the words of every paragraph into lines of at most `width` columns. A word longer than
+// that gets a line of its own.
+fn wrap(text: &str, width: usize) -> String {
+    let mut wrapped = Vec::new();
+    for paragraph in text.split("\n\n") {
+        let mut line = String::new();
+        let mut lines = Vec::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+        wrapped.push(lines.join("\n"));
+    }
+    wrapped.join("\n\n")
+}
+
+pub fn render(example: &Example) -> String {
+    let width = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(WIDTH);
+    let (prose, code) = example.lesson();
+    let prose = if prose.is_empty() {
+        "This example has no annotation."
+    } else {
+        &prose
+    };
+    format!(
+        "===== {}: {} =====\n\n{}\n\n{}\n",
+        example.name,
+        example.summary(),
+        wrap(prose, width),
+        code
+    )
+}
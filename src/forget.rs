@@ -0,0 +1,80 @@
+use crate::{tracker, util::DropSpy};
+use std::mem;
+
+// `mem::forget` takes ownership of a value and gives it to nobody, so nobody drops it: neither its
+// Drop nor its drop glue ever runs. It is not unsafe, because leaking is not undefined behavior.
+// Safe code must never rely on a destructor running for soundness. Compare with `drop`, which
+// takes ownership too but drops the value right away.
+#[allow(unused)]
+pub fn forget1() {
+    struct A();
+    struct B();
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped, but you will never see this line");
+        }
+    }
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped by drop(b)");
+        }
+    }
+
+    let a = A();
+    let b = B();
+    mem::forget(a);
+    drop(b);
+    println!("the end of the scope, where nothing is left to drop");
+}
+
+// What a forgotten value owns is forgotten too. Here the Box of `leaked` is never freed, which is
+// what the summary printed after the example is about: the heap is counted around every run, so
+// the 1024 bytes show up as still allocated. `cargo run -- timeline forget2` shows them, too.
+#[allow(unused)]
+pub fn forget2() {
+    struct A(Box<[u8; 1024]>);
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped, and the 1024 bytes it owns with it");
+        }
+    }
+
+    let kept = A(Box::new([0; 1024]));
+    let leaked = A(Box::new([0; 1024]));
+    drop(kept);
+    mem::forget(leaked);
+    println!("the other 1024 bytes are leaked");
+}
+
+// `Vec::drain` moves elements out of the middle of a Vec, and its Drop moves the tail back to
+// close the gap. If the Drain is forgotten, that Drop never runs, and the Vec would be left with
+// a hole of moved-out elements in it. So `drain` shortens the Vec to the start of the range
+// before it hands out anything, and only the Drop puts the tail back. Forgotten half-way, the
+// Drain leaves the Vec with what was before the range: the element not yet taken and the whole
+// tail are never dropped. That's leak amplification: leaking one value leaks more than it owns,
+// and that's fine, since leaking is safe and reading a moved-out element would not be.
+//
+// Leaking became safe in 2015, just before Rust 1.0, in what was called the leakpocalypse.
+// `thread::scoped` returned a JoinGuard that joined the thread in its Drop, so the thread could
+// borrow from the stack of its parent. But a JoinGuard in an Rc cycle is never dropped, and the
+// thread would go on using a stack that is long gone. Rather than find every way to leak, the
+// language let `mem::forget` be safe and made no API rely on a Drop for soundness. Scoped threads
+// came back with `thread::scope`, which joins the threads itself before it returns.
+#[allow(unused)]
+pub fn forget3() {
+    let mut vec = vec![
+        DropSpy("vec[0]"),
+        DropSpy("vec[1]"),
+        DropSpy("vec[2]"),
+        DropSpy("vec[3]"),
+    ];
+    let mut drain = vec.drain(1..3);
+    let taken = drain.next();
+    mem::forget(drain);
+    println!("{} element left in the vec", vec.len());
+    drop(taken);
+    // vec gets dropped here, with vec[0] only: vec[2] and vec[3] are leaked
+}
@@ -0,0 +1,114 @@
+use crate::util::DropSpy;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+// An async fn doesn't run when called: it returns a future, a state machine that holds its locals
+// while it waits at an `.await`. Someone has to poll it to make progress, and whoever owns it may
+// just as well drop it instead, which cancels it. Then its code never gets past the `.await`, but
+// the destructors of what it holds still run, like for any other value: first the future it was
+// awaiting, then its locals in reverse order. There is no executor here, `poll` is called by hand
+// with a waker that does nothing.
+#[allow(unused)]
+pub fn futures1() {
+    // Pending on the first poll, ready on the next
+    struct YieldOnce(&'static str, bool);
+    impl Future for YieldOnce {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.1 {
+                return Poll::Ready(());
+            }
+            self.1 = true;
+            Poll::Pending
+        }
+    }
+    impl Drop for YieldOnce {
+        fn drop(&mut self) {
+            crate::tracker::dropped(self.0);
+            println!("{} dropped", self.0);
+        }
+    }
+
+    async fn task(name: &'static str) {
+        let held = DropSpy(name);
+        YieldOnce("awaited", false).await;
+        println!("{} finished", name);
+    }
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut finished = Box::pin(task("finished task"));
+    while finished.as_mut().poll(&mut cx).is_pending() {
+        println!("the finished task is pending");
+    }
+    drop(finished);
+
+    let mut cancelled = Box::pin(task("cancelled task"));
+    let _ = cancelled.as_mut().poll(&mut cx);
+    println!("the cancelled task is pending");
+    drop(cancelled);
+    println!("the cancelled task never finished");
+}
+
+// The state machine only keeps what is still alive at an `.await`: that's what it has to hand
+// back to the code after it. A local dropped before, by hand or at the end of a block, never makes
+// it into the future. One that is held across the await lives in the future until the code after
+// the await is done with it, or until the future is dropped. It also takes room in the future,
+// which is why a big buffer is better dropped before awaiting.
+#[allow(unused)]
+pub fn futures2() {
+    use std::{hint::black_box, mem::size_of_val};
+
+    struct YieldOnce(bool);
+    impl Future for YieldOnce {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            let ready = self.0;
+            self.0 = true;
+            if ready {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    async fn task() {
+        let before = DropSpy("dropped before the await");
+        drop(before);
+        {
+            let scoped = DropSpy("scoped before the await");
+        }
+        let held = DropSpy("held across the await");
+        YieldOnce(false).await;
+        println!("after the await");
+    }
+    async fn buffer_held() {
+        let buffer = [0u8; 1024];
+        YieldOnce(false).await;
+        black_box(&buffer);
+    }
+    async fn buffer_dropped() {
+        {
+            let buffer = [0u8; 1024];
+            black_box(&buffer);
+        }
+        YieldOnce(false).await;
+    }
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = Box::pin(task());
+    let _ = future.as_mut().poll(&mut cx);
+    println!("the task is waiting at the await");
+    drop(future);
+    println!(
+        "a buffer held across the await is in the future: {}",
+        size_of_val(&buffer_held()) >= 1024
+    );
+    println!(
+        "a buffer dropped before the await is in the future: {}",
+        size_of_val(&buffer_dropped()) >= 1024
+    );
+}
@@ -0,0 +1,135 @@
+use crate::util::DropSpy;
+use std::{marker::PhantomData, mem::ManuallyDrop, panic, thread};
+
+// Drop runs on every way out of a scope: the end of the block, `return`, `?`, `break` and a panic
+// unwinding through it. A guard is a value whose only job is to have a Drop, so that some cleanup
+// runs on all of them without writing it on every path. The ScopeGuard below is small enough to be
+// copied into a project of your own, like the `defer!` macro on top of it.
+
+// When a guard runs its closure
+pub trait Strategy {
+    fn should_run() -> bool;
+}
+
+// Whatever happens
+pub enum Always {}
+// Only when the scope is left normally, e.g. to commit what the scope did
+pub enum OnSuccess {}
+// Only while unwinding from a panic, e.g. to roll back what the scope did
+pub enum OnUnwind {}
+
+impl Strategy for Always {
+    fn should_run() -> bool {
+        true
+    }
+}
+
+impl Strategy for OnSuccess {
+    fn should_run() -> bool {
+        !thread::panicking()
+    }
+}
+
+impl Strategy for OnUnwind {
+    fn should_run() -> bool {
+        thread::panicking()
+    }
+}
+
+// The closure is in a ManuallyDrop so that Drop can take it out by value, since a FnOnce can only
+// be called by value and Drop only gets a &mut self
+pub struct ScopeGuard<F: FnOnce(), S: Strategy = Always> {
+    cleanup: ManuallyDrop<F>,
+    _strategy: PhantomData<S>,
+}
+
+impl<F: FnOnce(), S: Strategy> ScopeGuard<F, S> {
+    pub fn new(cleanup: F) -> ScopeGuard<F, S> {
+        ScopeGuard {
+            cleanup: ManuallyDrop::new(cleanup),
+            _strategy: PhantomData,
+        }
+    }
+
+    // Disarm the guard: the closure is dropped without being called. The guard is wrapped in a
+    // ManuallyDrop first, so that its own Drop doesn't run and call the closure after all.
+    pub fn dismiss(self) {
+        let mut guard = ManuallyDrop::new(self);
+        // Safety: the closure is not used again, the guard is never dropped
+        unsafe { ManuallyDrop::drop(&mut guard.cleanup) };
+    }
+}
+
+impl<F: FnOnce(), S: Strategy> Drop for ScopeGuard<F, S> {
+    fn drop(&mut self) {
+        // Safety: the closure is not used again, this is the last use of the guard
+        let cleanup = unsafe { ManuallyDrop::take(&mut self.cleanup) };
+        if S::should_run() {
+            cleanup();
+        }
+    }
+}
+
+// Run the block at the end of the scope, like `defer` in Go or Zig. The guard gets a name of its
+// own, so that it lives to the end of the scope: `let _ = ...` would drop it right away.
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _deferred = ScopeGuard::<_, Always>::new(|| { $($body)* });
+    };
+}
+
+// The deferred blocks run when the scope is left, in reverse order like any other locals, and
+// whether it is left normally or by a panic. Both calls to `work` below run them.
+#[allow(unused)]
+pub fn guard1() {
+    fn work(fail: bool) {
+        defer! { println!("first deferred block"); }
+        defer! { println!("second deferred block"); }
+        if fail {
+            panic!("the work failed");
+        }
+        println!("the work is done");
+    }
+
+    work(false);
+    let result = panic::catch_unwind(|| work(true));
+    println!("caught the panic: {}", result.is_err());
+}
+
+// The strategy picks the paths the cleanup runs on. A transaction is rolled back while unwinding
+// and committed otherwise, with one guard for each.
+#[allow(unused)]
+pub fn guard2() {
+    fn transaction(fail: bool) {
+        let _commit = ScopeGuard::<_, OnSuccess>::new(|| println!("committed"));
+        let _rollback = ScopeGuard::<_, OnUnwind>::new(|| println!("rolled back"));
+        if fail {
+            panic!("the transaction failed");
+        }
+    }
+
+    transaction(false);
+    let result = panic::catch_unwind(|| transaction(true));
+    println!("caught the panic: {}", result.is_err());
+}
+
+// dismiss() is for when the cleanup turns out not to be needed, e.g. a temporary file that should
+// stay once everything worked. The closure is dropped without being called, and with it whatever
+// it captured, here a DropSpy.
+#[allow(unused)]
+pub fn guard3() {
+    let captured = DropSpy("captured by the dismissed guard");
+    let guard = ScopeGuard::<_, Always>::new(move || {
+        let spy = captured;
+        println!("cleaning up {}", spy.0);
+    });
+    guard.dismiss();
+    println!("the guard was dismissed");
+
+    let captured = DropSpy("captured by the kept guard");
+    let guard = ScopeGuard::<_, Always>::new(move || {
+        let spy = captured;
+        println!("cleaning up {}", spy.0);
+    });
+    println!("the end of the block");
+}
@@ -0,0 +1,179 @@
+// Counts the bytes and blocks on the heap, so that a leak shows up as a number rather than only in
+// a profile. Wrap the global allocator of a binary in `Counting` and compare `usage()` before and
+// after running an example to see what it left behind.
+//
+// The counters are shared by all threads. Only the difference between two readings means
+// anything: memory allocated before a reading and freed after it makes the difference negative.
+//
+// Between `record` and `stop`, every allocation and deallocation also takes a reading with a
+// timestamp, to see how the heap evolves over an example rather than only how it ends.
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    sync::{
+        atomic::{AtomicBool, AtomicIsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+pub struct Counting<A>(pub A);
+
+static BYTES: AtomicIsize = AtomicIsize::new(0);
+static BLOCKS: AtomicIsize = AtomicIsize::new(0);
+
+thread_local! {
+    // No destructor and a const initializer, so the allocator can read it without allocating
+    static PAUSED: Cell<bool> = const { Cell::new(false) };
+}
+
+fn count(bytes: usize, blocks: isize) {
+    if PAUSED.try_with(Cell::get).unwrap_or(true) {
+        return;
+    }
+    BYTES.fetch_add(bytes as isize * blocks, Ordering::Relaxed);
+    BLOCKS.fetch_add(blocks, Ordering::Relaxed);
+    if RECORDING.load(Ordering::Relaxed) {
+        // Growing the recording allocates too, which must not be recorded in turn
+        untracked(sample);
+    }
+    if LOGGING.load(Ordering::Relaxed) {
+        untracked(|| {
+            ALLOCATIONS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(bytes as isize * blocks)
+        });
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Counting<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc(layout);
+        if !ptr.is_null() {
+            count(layout.size(), 1);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(layout.size(), 1);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout);
+        count(layout.size(), -1);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = self.0.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            count(layout.size(), -1);
+            count(new_size, 1);
+        }
+        new
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub bytes: isize,
+    pub blocks: isize,
+}
+
+impl Usage {
+    // What was allocated in between and is still there
+    pub fn since(self, earlier: Usage) -> Usage {
+        Usage {
+            bytes: self.bytes - earlier.bytes,
+            blocks: self.blocks - earlier.blocks,
+        }
+    }
+}
+
+pub fn usage() -> Usage {
+    Usage {
+        bytes: BYTES.load(Ordering::Relaxed),
+        blocks: BLOCKS.load(Ordering::Relaxed),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sample {
+    // Since `record` was called
+    pub time: Duration,
+    pub usage: Usage,
+}
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static SAMPLES: Mutex<Option<(Instant, Vec<Sample>)>> = Mutex::new(None);
+
+fn sample() {
+    let mut samples = SAMPLES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((start, samples)) = &mut *samples {
+        samples.push(Sample {
+            time: start.elapsed(),
+            usage: usage(),
+        });
+    }
+}
+
+// Start recording, from a first reading at time zero
+pub fn record() {
+    untracked(|| {
+        *SAMPLES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some((Instant::now(), Vec::new()));
+        sample();
+    });
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+// Stop recording and take the readings since `record`, ending with one taken right now
+pub fn stop() -> Vec<Sample> {
+    RECORDING.store(false, Ordering::Relaxed);
+    untracked(|| {
+        sample();
+        SAMPLES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+            .map_or_else(Vec::new, |(_, samples)| samples)
+    })
+}
+
+static LOGGING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: Mutex<Vec<isize>> = Mutex::new(Vec::new());
+
+// Keep every allocation and free, for `dm::log` to print them. Printing right from the allocator
+// could run into a print that is allocating, so they are printed later, see `take_allocations`.
+pub fn log_allocations(on: bool) {
+    LOGGING.store(on, Ordering::Relaxed);
+}
+
+// The sizes allocated, positive, and freed, negative, since the last call
+pub fn take_allocations() -> Vec<isize> {
+    untracked(|| {
+        std::mem::take(
+            &mut *ALLOCATIONS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
+    })
+}
+
+// Run `f` without counting what the current thread allocates or frees, e.g. for the log of the
+// tracker, which is not part of any example
+pub fn untracked<R>(f: impl FnOnce() -> R) -> R {
+    let paused = PAUSED.with(|paused| paused.replace(true));
+    let result = f();
+    PAUSED.with(|cell| cell.set(paused));
+    result
+}
@@ -0,0 +1,102 @@
+use crate::{tracker, util::DropSpy};
+
+// `Box::leak` gives up the ownership of the Box and hands out a `&'static mut` to its content
+// instead. Since the content is never freed, the reference is valid for the rest of the program,
+// which is what 'static means. That is handy for data that must outlive everything, e.g. to be
+// shared with threads without an Arc. But a reference owns nothing, so dropping it drops nothing:
+// A is never dropped, and the summary after the example reports its 4 bytes as still allocated.
+#[allow(unused)]
+pub fn leak1() {
+    struct A(i32);
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped, but you will never see this line");
+        }
+    }
+
+    let a: &'static mut A = Box::leak(Box::new(A(42)));
+    a.0 += 1;
+    println!("a lives as long as the program: {}", a.0);
+    // a gets dropped here. It is only a reference, A stays where it is.
+}
+
+// Leaking can be undone. `Box::from_raw` turns the pointer back into a Box, which owns A again and
+// drops it as usual. It is unsafe because the compiler cannot tell that the pointer came from a
+// Box, nor that the leaked reference is not used anymore: both are up to you.
+#[allow(unused)]
+pub fn leak2() {
+    struct A(i32);
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped and freed, owned by the Box again");
+        }
+    }
+
+    let a: &'static mut A = Box::leak(Box::new(A(42)));
+    println!("A({}) is leaked", a.0);
+    // SAFETY: a comes from Box::leak and is not used after this line
+    let a = unsafe { Box::from_raw(a as *mut A) };
+    println!("A({}) is reclaimed", a.0);
+    // a gets dropped here, and A with it
+}
+
+// `std::process::exit` ends the process right where it is called. Unlike a panic it doesn't
+// unwind, and unlike a return from main it doesn't go back up through anybody's scope, so no
+// destructor on the stack of this thread or any other runs: the DropSpy values below are never
+// dropped, and whatever they would have flushed or cleaned up is lost. The runner doesn't get to
+// print its summary either, nor the dhat profiler to write its report, which is done by its Drop.
+// Return from main with an exit code instead, e.g. `fn main() -> ExitCode`, and let the
+// destructors run first.
+#[allow(unused)]
+pub fn leak3() {
+    fn returns() {
+        let a = DropSpy("a");
+        let b = DropSpy("b");
+        println!("returning normally");
+    }
+
+    returns();
+    let c = DropSpy("c");
+    let d = DropSpy("d");
+    println!("exiting the process");
+    std::process::exit(0);
+}
+
+// `Box::into_raw` is the way to hand a Box to code that only takes pointers, e.g. the `void *`
+// user data of a C callback. The raw pointer owns nothing, so nothing is dropped while it is
+// passed around, until `Box::from_raw` turns it back into a Box, which drops the payload once at
+// the end of its scope. Exactly once is up to us: a raw pointer is Copy, and nothing stops a
+// second `from_raw` on the same pointer. That compiles, and is a double free.
+#[allow(unused)]
+pub fn leak4() {
+    use std::ffi::c_void;
+
+    struct Payload(&'static str);
+    impl Drop for Payload {
+        fn drop(&mut self) {
+            tracker::dropped("Payload");
+            println!("Payload dropped by the Box it was turned back into");
+        }
+    }
+
+    // Stands for a C library calling back with the pointer it was registered with
+    fn call_back(user_data: *mut c_void, callback: fn(*mut c_void)) -> *mut c_void {
+        callback(user_data);
+        user_data
+    }
+
+    let raw = Box::into_raw(Box::new(Payload("user data")));
+    let returned = call_back(raw.cast(), |user_data| {
+        // SAFETY: the pointer came from Box::into_raw and the Box is not back yet
+        let payload = unsafe { &*user_data.cast::<Payload>() };
+        println!("the callback borrows the {}", payload.0);
+    });
+    // SAFETY: from Box::into_raw, and turned back into a Box only here
+    let boxed = unsafe { Box::from_raw(returned.cast::<Payload>()) };
+    println!("the {} is in a Box again", boxed.0);
+    // uncomment the following line to free it twice, then run `cargo run -- miri leak4`
+    // let again = unsafe { Box::from_raw(raw) };
+    // boxed gets dropped here, and the Payload with it
+}
@@ -0,0 +1,265 @@
+// Comprehend Drop Check in Rust by Examples
+//
+// Every topic is a module and every example is a function in it. Read these functions and their
+// annotations sequentially to build your mental model about the drop check system in Rust. You are
+// encouraged to add/delete/modify the code to see the warning/error from compiler and the output
+// of the program. Playing with it is beneficial to your understand.
+//
+// The binary in main.rs runs them by name, e.g. `cargo run -- may_dangle3`.
+
+// `#[may_dangle]`, coroutines and the Allocator trait are unstable, so the topics that need them
+// are behind the `nightly` feature
+#![cfg_attr(
+    feature = "nightly",
+    feature(
+        dropck_eyepatch,
+        coroutines,
+        coroutine_trait,
+        stmt_expr_attributes,
+        allocator_api
+    )
+)]
+
+// So that `#[derive(TraceDrop)]` can refer to `::dm` from inside this crate as well
+extern crate self as dm;
+
+#[cfg(feature = "nightly")]
+pub mod arena;
+pub mod closures;
+#[cfg(feature = "nightly")]
+pub mod coroutines;
+pub mod cycles;
+pub mod drop_flags;
+pub mod dropck;
+pub mod drop_glue;
+pub mod drop_order;
+pub mod forget;
+#[cfg(feature = "async")]
+pub mod futures;
+pub mod guard;
+pub mod heap;
+pub mod leak;
+pub mod locale;
+pub mod log;
+pub mod manually_drop;
+pub mod maybe_uninit;
+#[cfg(feature = "nightly")]
+pub mod myvec;
+#[cfg(feature = "nightly")]
+pub mod may_dangle;
+#[cfg(feature = "nightly")]
+pub mod phantom;
+#[cfg(feature = "nightly")]
+pub mod phantom_data;
+pub mod pin;
+pub mod quizzes;
+mod registry;
+pub mod replace;
+pub mod self_ref;
+pub mod set_len;
+pub mod shared;
+pub mod statics;
+pub mod std_guards;
+pub mod temporaries;
+pub mod threads;
+pub mod tracker;
+pub mod unwind;
+pub mod util;
+pub mod variance;
+
+pub use dm_derive::TraceDrop;
+use registry::register_examples;
+pub use registry::{Difficulty, Example};
+
+// All the examples in reading order, grouped by the module they live in.
+register_examples! {
+    drop_order (intro) {
+        drop_order: "declared first, drop last",
+        drop_order2: "parameters are dropped last in a call, the returned value at the caller",
+        drop_order3: "tuples, arrays and Vecs drop their elements front to back",
+    }
+    drop_glue (intro) {
+        drop_glue1: "Drop::drop runs first, then the drop glue of the fields",
+        drop_glue2: "drop glue only sticks owned members, not references",
+        drop_glue3: "drop glue processes owned members recursively",
+        drop_glue4: "the vtable of a trait object carries the drop glue of the concrete type",
+        drop_glue5: "needs_drop tells whether dropping a type does anything at all",
+        drop_glue6: "Copy types have no drop glue, a Drop newtype brings it back",
+        drop_glue7: "a zero-sized type is still dropped, even without any memory",
+        drop_glue8: "an enum drops the fields of the variant it holds, and only those",
+        drop_glue9: "a Box<dyn Any> runs the right Drop, downcast or not",
+        drop_glue10: "a T: Drop bound doesn't mean that dropping a T does anything",
+    }
+    #[cfg(feature = "nightly")]
+    may_dangle (advanced) {
+        may_dangle1: "a trivial drop lets references dangle",
+        may_dangle2: "an explicit Drop requires borrowed data to outlive it",
+        may_dangle3: "#[may_dangle] relaxes the check on a lifetime",
+        may_dangle4: "#[may_dangle] relaxes the check on a type parameter",
+        may_dangle5: "using a #[may_dangle] parameter in drop is undefined behavior",
+        may_dangle6: "#[may_dangle] doesn't apply to explicit drops",
+        may_dangle7: "#[may_dangle] doesn't allow dropping an owned field early",
+        may_dangle8: "HashMap and BTreeMap let borrowed keys and values dangle",
+        may_dangle9: "a map of our own with #[may_dangle] on both its parameters",
+    }
+    #[cfg(feature = "nightly")]
+    phantom (advanced) {
+        phantom1: "a Box-like struct over a raw pointer with #[may_dangle]",
+        phantom2: "#[may_dangle] without ownership of T visits a dangling reference",
+        phantom3: "PhantomData<T> tells the drop checker that T is owned",
+        phantom4: "drop_in_place drops a whole slice, or a dyn value through its vtable",
+        phantom5: "*mut T, NonNull<T> and Unique<T> differ in variance and ownership",
+        phantom6: "Box and Vec put #[may_dangle] on T but not on their allocator",
+    }
+    #[cfg(feature = "nightly")]
+    phantom_data (advanced) {
+        phantom_data1: "PhantomData<T> owns a T and is covariant",
+        phantom_data2: "PhantomData<&'a T> borrows a T, right for an iterator, wrong for a box",
+        phantom_data3: "PhantomData<*const T> makes a type neither Send nor Sync",
+        #[native]
+        phantom_data4: "PhantomData<fn(T) -> T> is invariant, and Send and Sync regardless of T",
+        phantom_data5: "a DropCounter checks that a box drops what it owns exactly once",
+    }
+    manually_drop (intermediate) {
+        manually_drop1: "ManuallyDrop<T> switches off the drop glue of T",
+        manually_drop2: "ManuallyDrop::drop is unsafe, call it once from the Drop of the owner",
+        manually_drop3: "moving out of a type with Drop the way std does it",
+        manually_drop4: "a union never drops its fields, the active one is dropped by hand",
+    }
+    forget (intro) {
+        forget1: "mem::forget skips Drop entirely, and it is safe",
+        forget2: "forgetting a value leaks what it owns on the heap",
+        forget3: "a forgotten Drain leaks the rest of the Vec, but never exposes a hole",
+    }
+    leak (intermediate) {
+        leak1: "Box::leak promotes a value to 'static and never drops it",
+        leak2: "Box::from_raw takes a leaked value back to drop it",
+        #[aborts]
+        leak3: "process::exit ends the process without running any destructor",
+        leak4: "Box::into_raw and Box::from_raw pass the ownership through a pointer",
+    }
+    cycles (intro) {
+        cycles1: "two Rc that own each other are never dropped",
+        cycles2: "Weak breaks the cycle by not owning",
+    }
+    unwind (intermediate) {
+        #[native]
+        unwind1: "a panic drops the locals that exist on its way up",
+        #[aborts]
+        unwind2: "a panic in Drop while unwinding aborts the process",
+        #[native]
+        unwind3: "catch_unwind stops the unwinding at the closure",
+    }
+    temporaries (intro) {
+        temporaries1: "temporaries are dropped at the end of the statement",
+        temporaries2: "a reference into a temporary must not outlive the statement",
+        temporaries3: "let extends a temporary it borrows to the end of the block",
+        temporaries4: "the temporaries of a match scrutinee live until the end of the match",
+        temporaries5: "if let drops its temporaries before else since edition 2024",
+        temporaries6: "let-else drops its temporaries before either path goes on",
+    }
+    closures (intro) {
+        closures1: "a move closure owns its captures and drops them when it is dropped",
+        closures2: "calling a FnOnce closure consumes it along with its captures",
+        closures3: "a closure called on drop must not borrow what is dropped before it",
+    }
+    drop_flags (intermediate) {
+        drop_flags1: "a drop flag remembers whether a variable was initialized",
+        drop_flags2: "a drop flag remembers whether a variable was moved out",
+        drop_flags3: "moving a field out leaves the others to drop, unless there is a Drop impl",
+    }
+    maybe_uninit (advanced) {
+        maybe_uninit1: "a MaybeUninit never drops what it holds, assume_init takes it back",
+        maybe_uninit2: "assume_init_drop drops in place, assume_init_read moves out",
+        #[native]
+        maybe_uninit3: "a guard drops the initialized part of an array when a panic cuts it short",
+    }
+    shared (intermediate) {
+        shared1: "a reference-counted pointer drops its value with the last clone",
+        #[native]
+        shared2: "an atomic count lets the last clone drop the value on another thread",
+        shared3: "Rc::into_raw and the manual counts decide when the value is dropped",
+    }
+    variance (intermediate) {
+        variance1: "a covariant lifetime shrinks to the shortest, which must outlive the value",
+        variance2: "an invariant lifetime can't shrink to let values of different lifetimes mix",
+    }
+    dropck (intermediate) {
+        dropck1: "the generic parameters of a type with Drop must strictly outlive it",
+        dropck2: "a closure borrows through its type, so the same rule applies to it",
+    }
+    #[cfg(feature = "nightly")]
+    arena (advanced) {
+        arena1: "values in an arena may borrow each other, thanks to #[may_dangle]",
+    }
+    self_ref (intermediate) {
+        self_ref1: "a pointer into its own field goes stale when the struct moves",
+        self_ref2: "a pinned value stays in place until it is dropped",
+    }
+    pin (advanced) {
+        pin1: "a pinned value must be dropped before its memory is reused",
+        pin2: "Drop for a !Unpin type goes through Pin::new_unchecked(self)",
+    }
+    #[cfg(feature = "async")]
+    futures (intermediate) {
+        futures1: "dropping a future cancels it and drops what it holds across .await",
+        futures2: "only the locals alive at an .await are kept in the future",
+    }
+    #[cfg(feature = "nightly")]
+    coroutines (advanced) {
+        coroutines1: "a suspended coroutine drops the locals alive at its yield",
+    }
+    guard (intermediate) {
+        #[native]
+        guard1: "deferred blocks run on every way out of a scope, panics included",
+        #[native]
+        guard2: "a guard's strategy picks whether it runs on success or on unwind",
+        guard3: "dismiss() disarms a guard by wrapping it in ManuallyDrop",
+    }
+    std_guards (intro) {
+        std_guards1: "a RefCell stays borrowed as long as its Ref or RefMut lives",
+        std_guards2: "let _ drops a MutexGuard right away, let _guard keeps the lock",
+        std_guards3: "a guard bound with let holds its lock to the end of the block",
+        #[native]
+        std_guards4: "a MutexGuard dropped while unwinding poisons its mutex",
+        std_guards5: "an Entry has no Drop, so its borrow ends at its last use",
+        #[native]
+        std_guards6: "poisoning is done by the Drop of the guard, while unwinding",
+    }
+    threads (intermediate) {
+        #[native]
+        threads1: "dropping a JoinHandle detaches its thread instead of joining it",
+        #[native]
+        threads2: "a detached thread that never ends never drops what it owns",
+        #[native]
+        threads3: "thread-locals are dropped when their thread exits, in some order",
+    }
+    statics (intro) {
+        statics1: "a static is never dropped, a const is dropped at every use",
+        statics2: "a OnceLock drops its value only if it was set, and only if it is local",
+        #[native]
+        statics3: "a LazyLock that is never forced, or that panics, has nothing to drop",
+    }
+    replace (intro) {
+        replace1: "assignment drops the old value, mem::replace hands it back",
+        replace2: "mem::swap moves two values around and drops neither",
+        replace3: "mem::take and Option::take move a value out through a &mut",
+    }
+    set_len (advanced) {
+        set_len1: "set_len to less forgets the elements past the new length",
+        set_len2: "set_len to too much leaves an element in two Vecs, which both drop it",
+        #[native]
+        set_len3: "a guard keeps the length right when filling a Vec by hand panics",
+    }
+}
+
+/// Looks an example up by the name of its function.
+///
+/// ```
+/// let example = dm::find("may_dangle3").unwrap();
+/// assert_eq!(example.topic, "may_dangle");
+/// assert!(dm::find("may_dangle42").is_none());
+/// ```
+pub fn find(name: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.name == name)
+}
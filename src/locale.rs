@@ -0,0 +1,84 @@
+// The annotations in other languages than English. A locale is a file in locales/ with a section
+// per translated example: its name in brackets, the one-line summary, an empty line and the
+// prose. Lines starting with `#` are comments. What a locale leaves out stays English, so a
+// translation can grow one example at a time.
+//
+// The locales are compiled in, for the web page as well. `Example::lesson` and
+// `Example::summary` speak whatever language was set last, English until then.
+
+use std::sync::Mutex;
+
+const LOCALES: &[(&str, &str)] = &[("de", include_str!("../locales/de.txt"))];
+
+static CURRENT: Mutex<&str> = Mutex::new("en");
+
+// English first, then the translations
+pub fn languages() -> Vec<&'static str> {
+    let mut languages = vec!["en"];
+    languages.extend(LOCALES.iter().map(|(lang, _)| *lang));
+    languages
+}
+
+pub fn set(lang: &str) -> Result<(), String> {
+    let lang = languages()
+        .into_iter()
+        .find(|known| *known == lang)
+        .ok_or_else(|| {
+            format!(
+                "unknown language `{}`, valid ones are: {}",
+                lang,
+                languages().join(", ")
+            )
+        })?;
+    *CURRENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = lang;
+    Ok(())
+}
+
+pub fn current() -> &'static str {
+    *CURRENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// The sections of a locale as (name, summary, prose)
+pub fn sections(text: &str) -> Vec<(&str, &str, String)> {
+    let mut sections = Vec::new();
+    let lines: Vec<_> = text.lines().filter(|line| !line.starts_with('#')).collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(name) = lines[i]
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        else {
+            i += 1;
+            continue;
+        };
+        let end = lines[i + 1..]
+            .iter()
+            .position(|line| line.starts_with('['))
+            .map_or(lines.len(), |end| i + 1 + end);
+        let mut body = lines[i + 1..end].iter().map(|line| line.trim_end());
+        let summary = body.find(|line| !line.is_empty()).unwrap_or("");
+        let prose: Vec<_> = body.collect();
+        sections.push((name, summary, prose.join("\n").trim().to_owned()));
+        i = end;
+    }
+    sections
+}
+
+// The summary and the prose of an example in the current language, if it is translated
+pub(crate) fn translation(name: &str) -> Option<(&'static str, String)> {
+    let lang = current();
+    let (_, text) = LOCALES.iter().find(|(locale, _)| *locale == lang)?;
+    sections(text)
+        .into_iter()
+        .find(|(section, _, _)| *section == name)
+        .map(|(_, summary, prose)| (summary, prose))
+}
+
+// Every locale by name, for the tests to check them against the examples
+pub fn locales() -> &'static [(&'static str, &'static str)] {
+    LOCALES
+}
@@ -0,0 +1,99 @@
+// How much the binary tells besides what the examples print, from `-q` to `-vv`. The detail comes
+// from the tracker, the types of util.rs and the heap, so no example has to change for it, and it
+// goes to stderr, out of the way of what the example prints:
+// - quiet, `-q`: nothing but a `<name> dropped` line per drop the tracker sees, see main.rs
+// - normal: what the examples print, and what they leaked
+// - verbose, `-v`: also every construction and drop the tracker sees, with the size and address
+//   of the value where it is known, e.g. for the types of util.rs
+// - trace, `-vv`: also every allocation and free on the heap in between
+
+use crate::heap;
+use std::{
+    fmt,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+    Trace,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+impl Verbosity {
+    // The option that sets it, for a child process to run with the same
+    pub fn flag(self) -> Option<&'static str> {
+        match self {
+            Verbosity::Quiet => Some("-q"),
+            Verbosity::Normal => None,
+            Verbosity::Verbose => Some("-v"),
+            Verbosity::Trace => Some("-vv"),
+        }
+    }
+
+    pub fn parse(flag: &str) -> Option<Verbosity> {
+        match flag {
+            "-q" | "--quiet" => Some(Verbosity::Quiet),
+            "-v" | "--verbose" => Some(Verbosity::Verbose),
+            "-vv" => Some(Verbosity::Trace),
+            _ => None,
+        }
+    }
+}
+
+pub fn set(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Trace,
+    }
+}
+
+// Print a line of detail if the verbosity is `$level` or more, e.g. `log!(Verbose, "{}", x)`
+macro_rules! log {
+    ($level:ident, $($arg:tt)*) => {
+        if $crate::log::verbosity() >= $crate::log::Verbosity::$level {
+            $crate::log::write(format_args!($($arg)*));
+        }
+    };
+}
+pub(crate) use log;
+
+// The allocations and frees since the last line come first, so that everything is in order
+pub fn write(args: fmt::Arguments) {
+    heap::untracked(|| {
+        flush();
+        eprintln!("[dm] {}", args);
+    });
+}
+
+// Log the allocations and frees from now on with `-vv`, e.g. from the start of an example
+pub fn start() {
+    heap::log_allocations(verbosity() >= Verbosity::Trace);
+}
+
+// Stop logging the allocations and frees, and print those not printed yet
+pub fn stop() {
+    heap::log_allocations(false);
+    flush();
+}
+
+fn flush() {
+    heap::untracked(|| {
+        for size in heap::take_allocations() {
+            if size < 0 {
+                eprintln!("[dm]   freed {} bytes", -size);
+            } else {
+                eprintln!("[dm]   allocated {} bytes", size);
+            }
+        }
+    });
+}
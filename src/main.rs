@@ -3,458 +3,478 @@
 // Run `make` to `cargo run` and generate a `profile.svg` that shows the details potential memory
 // leak. Note that the crate jemallocator seems to have memory leak itself(I don't know why. I'm
 // not an expert in it.) So don't freak out if you find some function call like
-// `_rjem_je_prof_backtrace` leaks 64B memory. It is not your fault.
+// `_rjem_je_prof_backtrace` leaks 64B memory. It is not your fault. If that's too much noise,
+// `make dhat` profiles the heap with dhat-rs instead. Where jemalloc doesn't build, pick another
+// allocator with the `alloc-mimalloc` or `alloc-system` feature. Without make, e.g. on Windows,
+// `cargo xtask profile <example>` takes care of the profile.
 //
-// Read these functions and their annotations sequentially to build your mental model about the drop
-// check system in Rust. You are encouraged to add/delete/modify the code to see the warning/error
-// from compiler and the output of the program. Playing with it is beneficial to your understand.
-// The main function is at the end of the file. Scroll down all the way to choose which function to
-// run.
+// The examples themselves live in the library, one module per topic, starting from lib.rs. Pass
+// the name of an example to choose which function to run, e.g. `cargo run -- may_dangle3`.
+// Running without any argument starts from the very first one. Besides the examples, it knows:
+// - list: print all the examples with a one-line summary
+// - run-all: run all the examples one after another
+// - check [exercise]: tell whether the exercises in exercises/ are fixed, see check.rs
+// - solution <exercise> [--diff]: show the solution of an exercise, or how it differs from yours
+// - reset: forget which examples were run and which quizzes answered right, see progress.rs
+// Both take `--topic=<topics>` and `--difficulty=<levels>` to go through a subset only, e.g.
+// `cargo run -- list --topic=drop_order,drop_glue --difficulty=intro`. The difficulty is one of
+// intro, intermediate and advanced, set per topic in lib.rs.
+// - quiz [example]: predict the drop order before running
+// - walkthrough [example]: predict the whole output before running
+// - explain <example>: print the annotation of an example, followed by its code
+// - view <example>: show the code with what each line did at runtime right under it
+// - dot <example>: draw who owns what as a Graphviz graph
+// - mir <example>: list the drops the compiler inserted, straight from the MIR
+// - elaborate <example>: show those drops under the source line they belong to
+// - miri <example>: run the example under Miri to catch undefined behavior
+// These three need a nightly toolchain, see mir.rs and miri.rs.
+// - abort [example]: compare the drops with panic=unwind and with panic=abort
+// - timeline <example>: chart the live bytes on the heap while the example runs
+// - tui: browse the examples in a terminal menu, with the `tui` feature
+// - completions <shell>: print the tab completion script for bash, zsh or fish
+// Add `--trace=<file>` to save what the tracker saw as JSON, and `--lang=<language>` to read the
+// annotations in another language, as far as they are translated in locales/. `-v` and `-vv` tell
+// more about what an example does, `-q` only what it drops, see log.rs.
+
+mod abort;
+mod check;
+mod completions;
+mod dot;
+mod explain;
+mod mir;
+mod miri;
+mod progress;
+mod quiz;
+mod scratch;
+mod solution;
+mod timeline;
+#[cfg(feature = "tui")]
+mod tui;
+mod view;
+mod walkthrough;
+
+use dm::{heap, tracker, Difficulty, Example, EXAMPLES};
+// Whichever allocator the features pick, it is counted, to tell how much an example leaves behind.
+// With the `dhat` feature, every example run also writes its own heap profile.
+#[cfg(feature = "dhat")]
+use dhat::Alloc as Allocator;
+#[cfg(all(
+    feature = "alloc-jemalloc",
+    unix,
+    not(any(feature = "dhat", feature = "alloc-mimalloc", feature = "alloc-system"))
+))]
+use jemallocator::Jemalloc as Allocator;
+#[cfg(all(feature = "alloc-mimalloc", not(feature = "dhat")))]
+use mimalloc::MiMalloc as Allocator;
+#[cfg(not(any(
+    feature = "dhat",
+    feature = "alloc-mimalloc",
+    all(feature = "alloc-jemalloc", unix, not(feature = "alloc-system"))
+)))]
+use std::alloc::System as Allocator;
 
-#![feature(dropck_eyepatch)]
-
-use jemallocator::Jemalloc;
-use std::{
-    alloc::{self, Layout},
-    fmt::Debug,
-    marker::PhantomData,
-    ptr,
-};
 #[global_allocator]
-static GLOBAL: Jemalloc = Jemalloc;
-
-// drop order: declared first, drop last
-#[allow(unused)]
-fn drop_order() {
-    struct A();
-    struct B();
-    impl Drop for A {
-        fn drop(&mut self) {
-            println!("A is dropped last because its declaration is the first")
-        }
+static GLOBAL: heap::Counting<Allocator> = heap::Counting(Allocator);
+
+// Run an example in a child process of this very binary and collect what it prints, so that
+// whatever it prints (or however it crashes) cannot mess up the caller.
+fn run_captured(name: &str) -> std::io::Result<String> {
+    let output = std::process::Command::new(std::env::current_exe()?)
+        .arg(name)
+        .output()?;
+    let mut captured = String::from_utf8_lossy(&output.stdout).into_owned();
+    captured.push_str(&String::from_utf8_lossy(&output.stderr));
+    if !output.status.success() {
+        captured.push_str(&format!("\n[{}]", output.status));
     }
-    impl Drop for B {
-        fn drop(&mut self) {
-            println!("B is dropped first although its initialization is earlier than A");
-        }
+    Ok(captured)
+}
+
+// Set in the process that an aborting example gets for itself
+const ISOLATED: &str = "DM_ISOLATED";
+
+// An example that aborts would take this process down with it, e.g. in the middle of `run-all`.
+// So it runs in a child process instead, and the abort is reported from here.
+fn isolate(example: &Example) {
+    let status = std::env::current_exe().and_then(|exe| {
+        std::process::Command::new(exe)
+            .arg(example.name)
+            .args(dm::log::verbosity().flag())
+            .env(ISOLATED, "1")
+            .status()
+    });
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("{} aborted its process: {}", example.name, status),
+        Err(err) => eprintln!("failed to start a process for {}: {}", example.name, err),
     }
-    let a;
-    let b = B();
-    a = A();
 }
 
-// The destructor in rust consists of two parts to help us automatically drop all the resources
-// owned by the object:
-// - the programmer customized function `Drop::drop`
-// - Drop glue that the compiler automatically attached for us
-// Run the following example to see how it works
+// Set in the process that runs an example for `-q`, see `run_quiet`
+const QUIET: &str = "DM_QUIET";
+const MARKER: char = '\u{1f}';
 
-#[allow(unused)]
-fn drop_glue1() {
-    struct A(B1, B2);
-    struct B1();
-    struct B2();
-    impl Drop for A {
-        // Even if you don't have a drop implementation, drop glue still applies to release the
-        // resources of its members. But here we have an explicit implementation though.
-        fn drop(&mut self) {
-            println!("Drop for A called");
-            // It is like the compiler automatically attaches sub-drop routine
-            // at the end of the drop using "glue".
-            println!("The following is the drop glue of A");
-        }
-    }
-    impl Drop for B1 {
-        fn drop(&mut self) {
-            println!("Drop for B1 called as part of the drop glue of A");
-            println!("No drop glue for B1 since it has no field");
-        }
-    }
-    impl Drop for B2 {
-        fn drop(&mut self) {
-            println!("Drop for B2 called as part of the drop glue of A");
-            println!("No drop glue for B2 since it has no field");
-        }
+fn mark_drop(event: &tracker::Event) {
+    if event.kind == tracker::Kind::Dropped {
+        println!("{}{}", MARKER, event.name);
     }
-
-    A(B1(), B2());
 }
 
-// A drop glue only sticks OWNED members. If a member is a reference, the resources of it should be
-// managed by its owner instead of the borrower. In simple words, who owns it drops it.
-#[allow(unused)]
-fn drop_glue2() {
-    struct A<'a>(&'a B1, B2);
-    struct B1();
-    struct B2();
-    impl<'a> Drop for A<'a> {
-        fn drop(&mut self) {
-            println!("Drop for A called");
-        }
-    }
-    impl Drop for B1 {
-        fn drop(&mut self) {
-            println!("Drop for B1 called NOT as part of the drop glue of A");
-            println!("Instead, this is called because its owner b1 is dropped");
+// With `-q`, the example runs in a child process that marks every drop the tracker sees in its
+// output. Only the drops are printed from here, as `<name> dropped`, e.g. to compare the drop
+// order across examples or toolchains without the prose around it.
+fn run_quiet(example: &Example) -> std::io::Result<std::process::ExitStatus> {
+    let output = std::process::Command::new(std::env::current_exe()?)
+        .arg(example.name)
+        .env(QUIET, "1")
+        .stderr(std::process::Stdio::null())
+        .output()?;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(name) = line.strip_prefix(MARKER) {
+            println!("{} dropped", name);
         }
     }
-    impl Drop for B2 {
-        // I'm called as part of drop glue of A.
-        fn drop(&mut self) {
-            println!("Drop for B2 called as part of the drop glue of A");
-        }
-    }
-
-    let b1 = B1();
-    A(&b1, B2());
+    Ok(output.status)
 }
 
-// The drop glue can process recursively if the owned member also owns a member
-#[allow(unused)]
-fn drop_glue3() {
-    struct A(B1);
-    struct B1(C1, C2);
-    struct C1();
-    struct C2();
-    impl Drop for A {
-        fn drop(&mut self) {
-            println!("Drop for A called");
-        }
+// Every example runs in a scope of its own in the tracker, and ends with what it leaked
+fn run(example: &Example) {
+    if std::env::var_os(QUIET).is_some() {
+        tracker::watch(mark_drop);
     }
-    impl Drop for B1 {
-        fn drop(&mut self) {
-            println!("Drop for B1 as part of the drop glue of A");
-            println!("because the ownership of b1 is transferred to A")
-        }
+    if example.aborts && std::env::var_os(ISOLATED).is_none() {
+        return isolate(example);
     }
-    impl Drop for C1 {
-        fn drop(&mut self) {
-            println!("Drop for C1 as part of the drop glue of B1");
-        }
+    // It only tells what it needs, there is nothing to track
+    if example.needs().is_some() {
+        return (example.run)();
     }
-    impl Drop for C2 {
-        fn drop(&mut self) {
-            println!("Drop for C1 as part of the drop glue of B1");
-        }
+    #[cfg(feature = "dhat")]
+    let _profiler = dhat::Profiler::builder()
+        .file_name(format!("dhat-{}.json", example.name))
+        .build();
+    // The buffer of stdout is there for good once it is first used, don't blame it on the example
+    let _ = std::io::stdout();
+    view::watch();
+    let before = heap::usage();
+    dm::log::start();
+    {
+        let _scope = tracker::enter();
+        (example.run)();
     }
-
-    let b1 = B1(C1(), C2());
-    A(b1);
+    dm::log::stop();
+    let leaked = heap::usage().since(before);
+    // Like the summary of a leak checker, out of the way of what the example prints
+    eprintln!(
+        "{} bytes in {} blocks still allocated",
+        leaked.bytes, leaked.blocks
+    );
 }
 
-// For more details about how drop glue works, check [the standard
-// library](https://doc.rust-lang.org/std/ops/trait.Drop.html#drop-check)
-
-// When drop is not explicitly implemented for a type, only drop glue would run. The compiler does
-// not check the lifetime for potentially dangling reference field because we can definitely ensure that
-// we would not access these reference fields during dropping. We do access owned members though.
-// But that's fine because we have full privilege over it.
-// We may call this kind of drop without implementation as `trivial drop`.
-#[allow(unused)]
-fn may_dangle1() {
-    struct A<'a>(&'a B);
-    struct B(i32);
-    impl Drop for B {
-        fn drop(&mut self) {
-            println!("B dropped here");
-        }
-    }
-
-    let a;
-    let b = B(42);
-    a = A(&b);
-    drop(b); 
-    println!("&b dangles henceforth");
-    println!("a would be dropped after this line");
-    // The dangling &b doesn't matter because this is a trivial drop which means we will
-    // not visit &a in destruction at all. So the compiler lets it go.
+// Which examples `list` and `run-all` go through, from `--topic=<topics>` and
+// `--difficulty=<levels>`, each a comma-separated list. Empty lets everything through.
+#[derive(Default)]
+struct Filter {
+    topics: Vec<String>,
+    difficulties: Vec<Difficulty>,
 }
 
-// When drop is explicitly implemented for a type, it requires this type outlives its reference
-// even though it is not necessary to do that semantically.
-#[allow(unused)]
-fn may_dangle2() {
-    struct A(i32);
-    struct B<'a>(&'a A);
-    impl<'a> Drop for B<'a> {
-        fn drop(&mut self) {
-            // even do nothing here
+impl Filter {
+    fn topics(&mut self, list: &str) -> Result<(), String> {
+        for topic in list.split(',') {
+            if !EXAMPLES.iter().any(|example| example.topic == topic) {
+                let mut topics: Vec<_> = EXAMPLES.iter().map(|example| example.topic).collect();
+                topics.dedup();
+                return Err(format!(
+                    "unknown topic `{}`, valid topics are: {}",
+                    topic,
+                    topics.join(", ")
+                ));
+            }
+            self.topics.push(topic.to_owned());
         }
+        Ok(())
     }
 
-    // uncomment te following three lines to what will happen
-    // let mut b;
-    // let a = A(42);
-    // b = B(&a);
-
-    // a gets dropped here
-    // b gets dropped here. a does not live long enough but we will not deref &a. So it should be
-    // OK effectively. But our compiler doesn't buy it.
-}
-
-// #[may_dangle] hints the compiler not to check the lifetime of 'a if the drop call is generated
-// by the compiler itself. But if we explicitly call the drop, the borrow check would raise an
-// compile error.
-#[allow(unused)]
-fn may_dangle3() {
-    struct A(i32);
-    struct B<'a>(&'a A);
-    impl Drop for A {
-        fn drop(&mut self) {
-            println!("A dropped");
-        }
-    }
-    unsafe impl<#[may_dangle] 'a> Drop for B<'a> {
-        fn drop(&mut self) {
-            println!("B dropped");
-        }
+    fn difficulties(&mut self, list: &str) -> Result<(), String> {
+        for name in list.split(',') {
+            let difficulty = Difficulty::parse(name).ok_or_else(|| {
+                format!(
+                    "unknown difficulty `{}`, valid ones are: intro, intermediate, advanced",
+                    name
+                )
+            })?;
+            self.difficulties.push(difficulty);
+        }
+        Ok(())
     }
 
-    let mut b;
-    let a = A(42);
-    b = B(&a);
-    // a gets dropped here
-    // b gets dropped here. But #[may_dangle] makes it compiles.
-    // What would happen if we call drop explicitly? Uncomment the following two lines.
-    // drop(a);
-    // drop(b);
-}
-
-// #[may_dangle] can be used to modify generics (after all, lifetime annotation is a variety of
-// generics)
-#[allow(unused)]
-fn may_dangle4() {
-    struct A(i32);
-    struct B<T>(T);
-    impl Drop for A {
-        fn drop(&mut self) {
-            println!("A dropped");
-        }
-    }
-    unsafe impl<#[may_dangle] T> Drop for B<T> {
-        // impl<T> Drop for B<T> { // What happens if you uncomment this line?
-        fn drop(&mut self) {
-            println!("B dropped");
-        }
+    fn examples(&self) -> impl Iterator<Item = &'static Example> + '_ {
+        EXAMPLES.iter().filter(|example| {
+            (self.topics.is_empty() || self.topics.iter().any(|topic| topic == example.topic))
+                && (self.difficulties.is_empty() || self.difficulties.contains(&example.difficulty))
+        })
     }
-
-    let mut b;
-    let a = A(42);
-    b = B(&a);
-    // a gets dropped here
-    // b gets dropped here. But #[may_dangle] makes it compiles.
 }
 
-// #[may_dangle] is unsafe which means you need to ensure that you would not use deref &A to avoid
-// undefined behaviors.
-#[allow(unused)]
-fn may_dangle5() {
-    struct B<T: Debug>(T);
-    unsafe impl<#[may_dangle] T: Debug> Drop for B<T> {
-        fn drop(&mut self) {
-            // Warning! You told the compiler that you would not use T again but you did! You'd
-            // probably get a random number other than 42. It is 0 or 1 in my system alloc. It may
-            // remains 42 in jemalloc. But that is not guarenteed in general.
-            println!("{:?}", self.0);
-        }
+fn list(filter: &Filter) {
+    let progress = progress::Progress::load();
+    let width = filter
+        .examples()
+        .map(|example| example.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut topic = "";
+    for example in filter.examples() {
+        if example.topic != topic {
+            topic = example.topic;
+            println!("{} ({}):", topic, example.difficulty.as_str());
+        }
+        println!(
+            "  {} {:width$}  {}",
+            progress.marks(example.name),
+            example.name,
+            example.summary()
+        );
     }
-
-    let mut b;
-    let a = Box::new(42);
-    b = B(&a);
-    // a gets dropped here
-    // b gets dropped here. But #[may_dangle] makes it compiles.
+    println!("{}", progress.summary());
 }
 
-// #[may_dangle] only applies to compiler-generated drop. If you drop variables
-// explicitly, drop checks still remain.
-#[allow(unused)]
-fn may_dangle6() {
-    struct B<T: Debug>(T);
-    unsafe impl<#[may_dangle] T: Debug> Drop for B<T> {
-        fn drop(&mut self) {
-            // nothing here
-        }
+// Run the whole tour in one go, e.g. to smoke-test it after editing. A banner tells where each
+// example starts, and a panicking example is reported at the end instead of stopping the tour.
+fn run_all(filter: &Filter) {
+    let (mut failed, mut missing) = (Vec::new(), Vec::new());
+    let examples: Vec<_> = filter.examples().collect();
+    for example in examples.iter().copied() {
+        println!("===== {}: {} =====", example.name, example.summary());
+        if example.needs().is_some() {
+            run(example);
+            missing.push(example.name);
+            println!();
+            continue;
+        }
+        let succeeded = if dm::log::verbosity() == dm::log::Verbosity::Quiet {
+            run_quiet(example).is_ok_and(|status| status.success())
+        } else {
+            std::panic::catch_unwind(|| run(example)).is_ok()
+        };
+        if !succeeded {
+            failed.push(example.name);
+        }
+        println!();
     }
-
-    let mut b;
-    let a = Box::new(42);
-    b = B(&a);
-    // Uncomment the following two lines to check it out.
-    // drop(a);
-    // drop(b);
-}
-
-// The following is another example of #[may_dangle] that shows this hint only applies in
-// compileer-generated drop. Dropping the owned  or referenced field directly in your
-// code makes it so dangerous that rustc decides not to indulge #[may_dangle].
-#[allow(unused)]
-fn may_dangle7() {
-    struct A();
-    struct B<T>(T); // T, which turns out to be A, is owned by B
-    unsafe impl<#[may_dangle] T> Drop for B<T> {
-        fn drop(&mut self) {
-            println!("B dropped");
-        }
+    let total = examples.len();
+    if !failed.is_empty() {
+        eprintln!(
+            "{} of {} examples panicked: {}",
+            failed.len(),
+            total,
+            failed.join(", ")
+        );
     }
-    impl Drop for A {
-        fn drop(&mut self) {
-            println!("A dropped as part of the drop glue of B");
-        }
+    if !missing.is_empty() {
+        eprintln!(
+            "{} of {} examples are not built in: {}",
+            missing.len(),
+            total,
+            missing.join(", ")
+        );
     }
-
-    let mut b;
-    let a = A();
-    b = B(a);
-    // Explicitly dropping a breaches the ownership system.
-    // drop(a); // Try to uncomment this line to see the error.
-}
-
-// Everything seems perfect right now -- except only one issue left.
-// To see the subtle pitfall, let us write a Box-like struct ourselves.
-#[allow(unused)]
-fn phantom1() {
-    struct MyBox<T>(*mut T); // T is not owned by MyVec because it's a pointer.
-    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
-        fn drop(&mut self) {
-            // If T is a reference type, simply free all the allocated space. We don't have to
-            // bother with dropping them since they are borrowed instead of owned. That's why we
-            // are going to add #[may_dangle] for the same reason as shown above.
-            // ptr::drop_in_place would do nothing to a reference. That's fine. references does
-            // nothing except for dropping the memory to store those references.
-            println!("a dropped");
-            unsafe {
-                ptr::drop_in_place(self.0);
-                alloc::dealloc(self.0 as *mut u8, Layout::new::<T>())
-            };
-        }
+    if !failed.is_empty() || !missing.is_empty() {
+        std::process::exit(1);
     }
-    impl<T> MyBox<T> {
-        fn new() -> MyBox<T> {
-            MyBox(unsafe { alloc::alloc(Layout::new::<T>()) } as *mut T)
-        }
-        fn move_in(&mut self, mut t: T) {
-            unsafe {
-                ptr::swap(self.0, &mut t as *mut T);
-            }
-        }
-    }
-
-    let mut a = MyBox::new();
-    let s = String::from("233");
-    a.move_in(&s);
-    drop(s);
-    println!("s dropped");
-    println!("&s dangles ever since");
-    // a get dropped here when &s is dangling
 }
 
-// On the other hand, if T is actually OWNED by MyBox like MyBox<T>, we must drop all of them
-// individually. So we really need to have mutable access to them and we hope our
-// compiler check works in such cases. However, #[may_dangle] skims through
-// the definition of struct MyVec<T> and say "T is not owned by MyVec. So I should take
-// effect", which makes the compiler ignores those checks.
-#[allow(unused)]
-fn phantom2() {
-    struct MyBox<T>(*mut T);
-    struct PrintOnDrop<'s>(&'s str);
-    impl<'s> Drop for PrintOnDrop<'s> {
-        fn drop(&mut self) {
-            println!("PrintOnDrop dropped as part of drop glue of MyBox");
-            println!("visit a dangling reference: {}", self.0);
-        }
-    }
-    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
-    // impl<T> Drop for MyBox<T> { // If this line uncommented?
-        fn drop(&mut self) {
-            println!("MyBox dropped");
-            unsafe {
-                ptr::drop_in_place(self.0);
-                alloc::dealloc(self.0 as *mut u8, Layout::new::<T>())
-            };
+pub fn main() {
+    // The options may come anywhere, everything else is positional
+    let mut trace = None;
+    let mut filter = Filter::default();
+    let mut args = Vec::new();
+    for arg in std::env::args().skip(1) {
+        let result = if let Some(path) = arg.strip_prefix("--trace=") {
+            trace = Some(path.to_owned());
+            Ok(())
+        } else if let Some(lang) = arg.strip_prefix("--lang=") {
+            dm::locale::set(lang)
+        } else if let Some(topics) = arg.strip_prefix("--topic=") {
+            filter.topics(topics)
+        } else if let Some(difficulties) = arg.strip_prefix("--difficulty=") {
+            filter.difficulties(difficulties)
+        } else if let Some(verbosity) = dm::log::Verbosity::parse(&arg) {
+            dm::log::set(verbosity);
+            Ok(())
+        } else {
+            args.push(arg);
+            Ok(())
+        };
+        if let Err(err) = result {
+            eprintln!("{}", err);
+            std::process::exit(2);
         }
     }
-    impl<T> MyBox<T> {
-        fn new() -> MyBox<T> {
-            MyBox(unsafe { alloc::alloc(Layout::new::<T>()) } as *mut T)
+    let name = args.first().map_or(EXAMPLES[0].name, String::as_str);
+    let arg = args.get(1).map(String::as_str);
+
+    match name {
+        "list" | "--list" => list(&filter),
+        "run-all" => run_all(&filter),
+        "walkthrough" => walkthrough::run(arg),
+        "quiz" => quiz::run(arg),
+        "mir" | "elaborate" | "miri" => match arg.and_then(dm::find) {
+            Some(example) => {
+                let result = match name {
+                    "mir" => mir::run(example),
+                    "elaborate" => mir::elaborate(example),
+                    _ => miri::run(example),
+                };
+                if let Err(err) = result {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+            None => {
+                eprintln!(
+                    "usage: {} <example>, run with `list` to see them all, on a nightly toolchain",
+                    name
+                );
+                std::process::exit(2);
+            }
+        },
+        "abort" => {
+            let example = arg.map(|name| {
+                dm::find(name).unwrap_or_else(|| {
+                    eprintln!("usage: abort [example], run with `list` to see them all");
+                    std::process::exit(2);
+                })
+            });
+            if let Err(err) = abort::run(example) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
         }
-        fn move_in(&mut self, mut t: T) {
-            unsafe {
-                std::mem::forget(std::mem::replace(&mut *self.0, t));
+        "timeline" => match arg.and_then(dm::find) {
+            Some(example) => timeline::run(example),
+            None => {
+                eprintln!("usage: timeline <example>, run with `list` to see them all");
+                std::process::exit(2);
+            }
+        },
+        "explain" => match arg.and_then(dm::find) {
+            Some(example) => print!("{}", explain::render(example)),
+            None => {
+                eprintln!("usage: explain <example>, run with `list` to see them all");
+                std::process::exit(2);
+            }
+        },
+        "view" => match arg.and_then(dm::find) {
+            Some(example) => {
+                if let Err(err) = view::run(example) {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+            None => {
+                eprintln!("usage: view <example>, run with `list` to see them all");
+                std::process::exit(2);
+            }
+        },
+        "dot" => match arg.and_then(dm::find) {
+            Some(example) => print!("{}", dot::render(example)),
+            None => {
+                eprintln!("usage: dot <example>, run with `list` to see them all");
+                std::process::exit(2);
+            }
+        },
+        #[cfg(feature = "tui")]
+        "tui" => tui::run().expect("failed to drive the terminal"),
+        #[cfg(not(feature = "tui"))]
+        "tui" => {
+            eprintln!("the menu is behind a feature, try `cargo run --features tui -- tui`");
+            std::process::exit(2);
+        }
+        "check" => match check::run(arg) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                eprintln!("{}", err);
+                std::process::exit(2);
+            }
+            Err(err) => {
+                eprintln!("failed to check the exercises: {}", err);
+                std::process::exit(1);
+            }
+        },
+        "solution" => {
+            let names = check::names(&check::dir()).unwrap_or_default();
+            match arg.filter(|arg| names.iter().any(|name| name == arg)) {
+                Some(exercise) => {
+                    let diff = args[2..].iter().any(|arg| arg == "--diff");
+                    if let Err(err) = solution::run(exercise, diff) {
+                        eprintln!("failed to show the solution of {}: {}", exercise, err);
+                        std::process::exit(1);
+                    }
+                }
+                None => {
+                    eprintln!(
+                        "usage: solution <exercise> [--diff], one of: {}",
+                        names.join(", ")
+                    );
+                    std::process::exit(2);
+                }
             }
         }
+        "completions" => match arg.and_then(completions::script) {
+            Some(script) => print!("{}", script),
+            None => {
+                eprintln!(
+                    "usage: completions <shell>, one of: {}",
+                    completions::SHELLS.join(", ")
+                );
+                std::process::exit(2);
+            }
+        },
+        "reset" => {
+            if let Err(err) = progress::reset() {
+                eprintln!("failed to reset the progress: {}", err);
+                std::process::exit(1);
+            }
+            println!("the progress is reset, every example is new again");
+        }
+        _ => match dm::find(name) {
+            Some(example) => {
+                progress::record(progress::Step::Ran, example.name);
+                if dm::log::verbosity() == dm::log::Verbosity::Quiet {
+                    match run_quiet(example) {
+                        Ok(status) if status.success() => {}
+                        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+                        Err(err) => {
+                            eprintln!("failed to start a process for {}: {}", example.name, err);
+                            std::process::exit(1);
+                        }
+                    }
+                } else {
+                    run(example);
+                }
+                // It only told what it needs, which is no success
+                if example.needs().is_some() {
+                    std::process::exit(2);
+                }
+            }
+            None => {
+                let names: Vec<_> = EXAMPLES.iter().map(|example| example.name).collect();
+                eprintln!("unknown example `{}`", name);
+                eprintln!("valid names are: {}", names.join(", "));
+                eprintln!("run with `list` to see what each of them is about");
+                std::process::exit(2);
+            }
+        },
     }
 
-    let mut a = MyBox::new();
-    let mut s = "Hello".to_owned();
-    s.push_str(" world");
-    a.move_in(PrintOnDrop(s.as_str()));
-    drop(s);
-    println!("s dropped");
-    // MyBox dropped here
-    // run the code to see the output.
-}
-
-// The point to resolve this trouble is to make T owned by MyVec in some way. Something tricky like
-// a zero-sized array in C language might have it settled but in rust we have a dedicated type,
-// PhantomData<T>, for this. What is
-// [PhantomData](https://doc.rust-lang.org/std/marker/struct.PhantomData.html)?
-#[allow(unused)]
-fn phantom3() {
-    struct MyBox<T>(*mut T, PhantomData<T>);
-    struct PrintOnDrop<'s>(&'s str);
-    impl<'s> Drop for PrintOnDrop<'s> {
-        fn drop(&mut self) {
-            println!("PrintOnDrop dropped as part of drop glue of MyBox");
-            println!("visit a dangling reference: {}", self.0);
+    if let Some(path) = trace {
+        let json = tracker::to_json(&tracker::take());
+        if let Err(err) = std::fs::write(&path, json) {
+            eprintln!("failed to write the trace to {}: {}", path, err);
+            std::process::exit(1);
         }
     }
-    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
-        fn drop(&mut self) {
-            println!("MyBox dropped");
-            unsafe {
-                ptr::drop_in_place(self.0);
-                alloc::dealloc(self.0 as *mut u8, Layout::new::<T>())
-            };
-        }
-    }
-    impl<T> MyBox<T> {
-        fn new() -> MyBox<T> {
-            MyBox(
-                unsafe { alloc::alloc(Layout::new::<T>()) } as *mut T,
-                PhantomData::default(),
-            )
-        }
-        fn move_in(&mut self, mut t: T) {
-            unsafe {
-                std::mem::forget(std::mem::replace(&mut *self.0, t));
-            }
-        }
-    }
-
-    // uncomment the following lines to see what happens;
-    // let mut a = MyBox::new();
-    // let mut s = "Hello".to_owned();
-    // s.push_str(" world");
-    // a.move_in(PrintOnDrop(s.as_str()));
-    // drop(s);
-    // println!("s dropped");
-}
-
-pub fn main() {
-    // Uncomment them to run
-    drop_order();
-    // drop_glue1();
-    // drop_glue2();
-    // drop_glue3();
-    // may_dangle1();
-    // may_dangle2();
-    // may_dangle3();
-    // may_dangle4();
-    // may_dangle5();
-    // may_dangle6();
-    // may_dangle7();
-    // phantom1();
-    // phantom2();
-    // phantom3();
 }
@@ -0,0 +1,140 @@
+use crate::{tracker, util::DropSpy};
+use std::{mem::ManuallyDrop, ptr};
+
+// `ManuallyDrop<T>` is a wrapper that switches off the drop glue for the `T` inside it. The value
+// is still owned, it just never gets dropped unless you ask for it. The fields of A are dropped by
+// its drop glue as usual, except for the one wrapped in ManuallyDrop.
+#[allow(unused)]
+pub fn manually_drop1() {
+    struct A {
+        b: ManuallyDrop<B>,
+        c: C,
+    }
+    struct B();
+    struct C();
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped");
+        }
+    }
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped, but you will never see this line");
+        }
+    }
+    impl Drop for C {
+        fn drop(&mut self) {
+            tracker::dropped("C");
+            println!("C dropped as part of the drop glue of A");
+        }
+    }
+
+    let a = A {
+        b: ManuallyDrop::new(B()),
+        c: C(),
+    };
+    // a gets dropped here, a.b is skipped by the drop glue. It is leaked, which is safe.
+}
+
+// Nothing stops you from dropping the value yourself with `ManuallyDrop::drop`. It is unsafe
+// because the compiler can no longer make sure that it happens exactly once: drop it twice and
+// it is a double free, use it afterwards and it is a use after free. The natural place to call it
+// is therefore the Drop of the owner, where the field is never touched again.
+#[allow(unused)]
+pub fn manually_drop2() {
+    struct A {
+        b: ManuallyDrop<B>,
+        c: C,
+    }
+    struct B();
+    struct C();
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped");
+            // SAFETY: self.b is dropped only here, and self is gone right after
+            unsafe { ManuallyDrop::drop(&mut self.b) };
+        }
+    }
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped by hand in the Drop of A");
+        }
+    }
+    impl Drop for C {
+        fn drop(&mut self) {
+            tracker::dropped("C");
+            println!("C dropped as part of the drop glue of A");
+        }
+    }
+
+    let a = A {
+        b: ManuallyDrop::new(B()),
+        c: C(),
+    };
+}
+
+// You cannot move a field out of a type that implements Drop, because its Drop would then see a
+// moved-out field. The standard library gets around it with ManuallyDrop: wrap the whole value so
+// that its Drop never runs, then read the fields out of it. `Box::into_raw`, `Vec::into_raw_parts`
+// and even `mem::forget` are built this way.
+#[allow(unused)]
+pub fn manually_drop3() {
+    struct Guard(B);
+    struct B();
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            tracker::dropped("Guard");
+            println!("Guard dropped, but not when it is turned into its B");
+        }
+    }
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped at the end of the scope, owned by b now");
+        }
+    }
+    impl Guard {
+        fn into_inner(self) -> B {
+            let guard = ManuallyDrop::new(self);
+            // SAFETY: guard is never dropped, so this is the only copy of the B that will be used
+            unsafe { ptr::read(&guard.0) }
+        }
+    }
+
+    let b = Guard(B()).into_inner();
+    println!("Guard is gone");
+}
+
+// A union holds one of its fields at a time, and nothing records which. So it can't drop the
+// active field when it is dropped itself, nor the old value when a field is assigned. That's why
+// every field of a union must either be Copy, with nothing to drop, or come in a ManuallyDrop,
+// which says that dropping it is up to us. Overwriting the text below leaks the first DropSpy,
+// and the second one is only dropped because we know it's the active field and drop it by hand.
+#[allow(unused)]
+pub fn manually_drop4() {
+    union Slot {
+        number: u32,
+        text: ManuallyDrop<DropSpy>,
+    }
+    // uncomment the following three lines to see the error (or read tests/ui/manually_drop4.stderr)
+    // union Wrong {
+    //     text: DropSpy,
+    // }
+
+    let mut slot = Slot {
+        text: ManuallyDrop::new(DropSpy("first")),
+    };
+    // Assigning a field is safe, since it doesn't drop what was there
+    slot.text = ManuallyDrop::new(DropSpy("second"));
+    println!("first was overwritten without being dropped");
+    // SAFETY: text is the active field, and it is not read again before number is written
+    unsafe { ManuallyDrop::drop(&mut slot.text) };
+    slot.number = 7;
+    // SAFETY: number is the active field now
+    println!("the slot holds {} now", unsafe { slot.number });
+    // slot gets dropped here, which does nothing at all
+}
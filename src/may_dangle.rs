@@ -0,0 +1,261 @@
+use crate::tracker;
+use std::fmt::Debug;
+
+// When drop is not explicitly implemented for a type, only drop glue would run. The compiler does
+// not check the lifetime for potentially dangling reference field because we can definitely ensure that
+// we would not access these reference fields during dropping. We do access owned members though.
+// But that's fine because we have full privilege over it.
+// We may call this kind of drop without implementation as `trivial drop`.
+#[allow(unused, clippy::needless_late_init)]
+pub fn may_dangle1() {
+    struct A<'a>(&'a B);
+    struct B(i32);
+    impl Drop for B {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped here");
+        }
+    }
+
+    let a;
+    let b = B(42);
+    a = A(&b);
+    drop(b); 
+    println!("&b dangles henceforth");
+    println!("a would be dropped after this line");
+    // The dangling &b doesn't matter because this is a trivial drop which means we will
+    // not visit &a in destruction at all. So the compiler lets it go.
+}
+
+// When drop is explicitly implemented for a type, it requires this type outlives its reference
+// even though it is not necessary to do that semantically.
+#[allow(unused)]
+pub fn may_dangle2() {
+    struct A(i32);
+    struct B<'a>(&'a A);
+    impl<'a> Drop for B<'a> {
+        fn drop(&mut self) {
+            // even do nothing here
+        }
+    }
+
+    // uncomment te following three lines to what will happen (or read tests/ui/may_dangle2.stderr)
+    // let mut b;
+    // let a = A(42);
+    // b = B(&a);
+
+    // a gets dropped here
+    // b gets dropped here. a does not live long enough but we will not deref &a. So it should be
+    // OK effectively. But our compiler doesn't buy it.
+}
+
+// #[may_dangle] hints the compiler not to check the lifetime of 'a if the drop call is generated
+// by the compiler itself. But if we explicitly call the drop, the borrow check would raise an
+// compile error.
+#[allow(unused)]
+pub fn may_dangle3() {
+    struct A(i32);
+    struct B<'a>(&'a A);
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped");
+        }
+    }
+    unsafe impl<#[may_dangle] 'a> Drop for B<'a> {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped");
+        }
+    }
+
+    let mut b;
+    let a = A(42);
+    b = B(&a);
+    // a gets dropped here
+    // b gets dropped here. But #[may_dangle] makes it compiles.
+    // What would happen if we call drop explicitly? Uncomment the following two lines. The answer
+    // is pinned in tests/ui/may_dangle3.stderr too.
+    // drop(a);
+    // drop(b);
+}
+
+// #[may_dangle] can be used to modify generics (after all, lifetime annotation is a variety of
+// generics)
+#[allow(unused)]
+pub fn may_dangle4() {
+    struct A(i32);
+    struct B<T>(T);
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped");
+        }
+    }
+    unsafe impl<#[may_dangle] T> Drop for B<T> {
+        // impl<T> Drop for B<T> { // What happens if you uncomment this line?
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped");
+        }
+    }
+
+    let mut b;
+    let a = A(42);
+    b = B(&a);
+    // a gets dropped here
+    // b gets dropped here. But #[may_dangle] makes it compiles.
+}
+
+// #[may_dangle] is unsafe which means you need to ensure that you would not use deref &A to avoid
+// undefined behaviors. `cargo run -- miri may_dangle5` has Miri point at the very read.
+#[allow(unused)]
+pub fn may_dangle5() {
+    struct B<T: Debug>(T);
+    unsafe impl<#[may_dangle] T: Debug> Drop for B<T> {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            // Warning! You told the compiler that you would not use T again but you did! You'd
+            // probably get a random number other than 42. It is 0 or 1 in my system alloc. It may
+            // remains 42 in jemalloc. But that is not guarenteed in general.
+            println!("B reads freed memory: {:?}", self.0);
+        }
+    }
+
+    let mut b;
+    let a = Box::new(42);
+    b = B(&a);
+    // a gets dropped here
+    // b gets dropped here. But #[may_dangle] makes it compiles.
+}
+
+// #[may_dangle] only applies to compiler-generated drop. If you drop variables
+// explicitly, drop checks still remain.
+#[allow(unused)]
+pub fn may_dangle6() {
+    struct B<T: Debug>(T);
+    unsafe impl<#[may_dangle] T: Debug> Drop for B<T> {
+        fn drop(&mut self) {
+            // nothing here
+        }
+    }
+
+    let mut b;
+    let a = Box::new(42);
+    b = B(&a);
+    // Uncomment the following two lines to check it out, or read tests/ui/may_dangle6.stderr.
+    // drop(a);
+    // drop(b);
+}
+
+// The following is another example of #[may_dangle] that shows this hint only applies in
+// compileer-generated drop. Dropping the owned  or referenced field directly in your
+// code makes it so dangerous that rustc decides not to indulge #[may_dangle].
+#[allow(unused)]
+pub fn may_dangle7() {
+    struct A();
+    struct B<T>(T); // T, which turns out to be A, is owned by B
+    unsafe impl<#[may_dangle] T> Drop for B<T> {
+        fn drop(&mut self) {
+            tracker::dropped("B");
+            println!("B dropped");
+        }
+    }
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped as part of the drop glue of B");
+        }
+    }
+
+    let mut b;
+    let a = A();
+    b = B(a);
+    // Explicitly dropping a breaches the ownership system.
+    // drop(a); // Try to uncomment this line to see the error. It's in tests/ui/may_dangle7.stderr.
+}
+
+// The maps of std have #[may_dangle] on their Drop too, on both the key and the value. So a map
+// of references may be dropped after what it borrows, like `keys` and `values` below, since a
+// reference has nothing to drop. PrintOnDrop has a Drop of its own, which reads the str it
+// borrows, and the eyepatch of the map doesn't reach into that: a map of PrintOnDrop must be
+// dropped before the String, i.e. declared after it.
+#[allow(unused, clippy::needless_late_init)]
+pub fn may_dangle8() {
+    use std::collections::{BTreeMap, HashMap};
+
+    struct PrintOnDrop<'s>(&'s str);
+    impl Drop for PrintOnDrop<'_> {
+        fn drop(&mut self) {
+            tracker::dropped("PrintOnDrop");
+            println!("PrintOnDrop dropped, it still reads {}", self.0);
+        }
+    }
+
+    let keys;
+    let values;
+    let s = String::from("s");
+    keys = BTreeMap::from([(s.as_str(), 1)]);
+    values = HashMap::from([(1, s.as_str())]);
+    let printed = HashMap::from([(1, PrintOnDrop(&s))]);
+    // uncomment the following three lines to see the error (or read tests/ui/may_dangle8.stderr)
+    // let late;
+    // let t = String::from("t");
+    // late = HashMap::from([(1, PrintOnDrop(&t))]);
+    println!(
+        "the maps hold {} borrows of s",
+        keys.len() + values.len() + printed.len()
+    );
+    // printed gets dropped here, while s is still there
+    // s gets dropped here
+    // values and keys get dropped here, their borrows dangle but nobody looks at them
+}
+
+// A map of our own behaves the same once its Drop has the eyepatch on both parameters. The Vec
+// inside owns the entries, so the drop checker knows that MiniMap drops K's and V's, and the
+// Drop itself only looks at the length. A MiniMap of PrintOnDrop is still held to the rule.
+#[allow(unused, clippy::needless_late_init)]
+pub fn may_dangle9() {
+    struct MiniMap<K, V> {
+        entries: Vec<(K, V)>,
+    }
+    impl<K: PartialEq, V> MiniMap<K, V> {
+        fn new() -> MiniMap<K, V> {
+            MiniMap {
+                entries: Vec::new(),
+            }
+        }
+        fn insert(&mut self, key: K, value: V) {
+            self.entries.retain(|(existing, _)| *existing != key);
+            self.entries.push((key, value));
+        }
+    }
+    unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for MiniMap<K, V> {
+        fn drop(&mut self) {
+            tracker::dropped("MiniMap");
+            println!("MiniMap dropped with {} entries", self.entries.len());
+        }
+    }
+    struct PrintOnDrop<'s>(&'s str);
+    impl Drop for PrintOnDrop<'_> {
+        fn drop(&mut self) {
+            tracker::dropped("PrintOnDrop");
+            println!("PrintOnDrop dropped, it still reads {}", self.0);
+        }
+    }
+
+    let mut map;
+    let s = String::from("s");
+    map = MiniMap::new();
+    map.insert(s.as_str(), 1);
+    let mut printed = MiniMap::new();
+    printed.insert(1, PrintOnDrop(&s));
+    // uncomment the following four lines to see the error (or read tests/ui/may_dangle9.stderr)
+    // let mut late;
+    // let t = String::from("t");
+    // late = MiniMap::new();
+    // late.insert(1, PrintOnDrop(&t));
+    // printed gets dropped here, while s is still there
+    // s gets dropped here
+    // map gets dropped here, its borrow dangles but nobody looks at it
+}
@@ -0,0 +1,85 @@
+use crate::util::DropSpy;
+use std::mem::MaybeUninit;
+
+// `MaybeUninit<T>` is room for a T that may or may not hold one. The compiler can't tell, so it
+// never drops what is inside: dropping a MaybeUninit does nothing, like ManuallyDrop. Once a value
+// is written, `assume_init` promises that it is there and hands it back as a plain T, which is
+// dropped as usual. Forget to call it and the value is leaked.
+#[allow(unused)]
+pub fn maybe_uninit1() {
+    let mut forgotten = MaybeUninit::<DropSpy>::uninit();
+    forgotten.write(DropSpy("forgotten"));
+
+    let mut slot = MaybeUninit::<DropSpy>::uninit();
+    slot.write(DropSpy("written"));
+    // Safety: the value was written just above
+    let value = unsafe { slot.assume_init() };
+    println!("{} is a DropSpy again", value.0);
+    // value gets dropped here
+    // forgotten gets dropped here, which drops nothing
+}
+
+// The value doesn't have to be taken out to be dropped. `assume_init_drop` drops it in place,
+// after which the MaybeUninit is uninitialized again and can be written anew. `assume_init_read`
+// copies the value out, so the caller is now responsible for exactly one of the two copies: here
+// the one that was read, since the MaybeUninit never drops anything on its own.
+#[allow(unused)]
+pub fn maybe_uninit2() {
+    let mut slot = MaybeUninit::<DropSpy>::uninit();
+    slot.write(DropSpy("first"));
+    // Safety: initialized, and not used again before the next write
+    unsafe { slot.assume_init_drop() };
+    println!("the slot is empty again");
+    slot.write(DropSpy("second"));
+    // Safety: initialized, and the copy left behind is never used or dropped
+    let second = unsafe { slot.assume_init_read() };
+    println!("read {} out of the slot", second.0);
+    // second gets dropped here, the slot drops nothing
+}
+
+// Filling an array of MaybeUninit one element at a time is how an array is built from a function
+// that may panic, e.g. `std::array::from_fn`. If it panics halfway, the elements written so far
+// must be dropped, and only those: the rest is garbage. Nobody else knows how far it got, so a
+// guard does. It owns the count of initialized elements and drops that prefix in its own Drop,
+// which runs while unwinding. Once the array is complete, the guard is forgotten and the array
+// taken out with every element initialized.
+#[allow(unused)]
+pub fn maybe_uninit3() {
+    use std::{panic, ptr};
+
+    struct Guard<'a, T> {
+        slots: &'a mut [MaybeUninit<T>],
+        initialized: usize,
+    }
+    impl<T> Drop for Guard<'_, T> {
+        fn drop(&mut self) {
+            println!("the guard drops the first {} elements", self.initialized);
+            let prefix = &mut self.slots[..self.initialized];
+            // Safety: the first `initialized` elements were written and nothing else drops them
+            unsafe { ptr::drop_in_place(prefix as *mut [MaybeUninit<T>] as *mut [T]) };
+        }
+    }
+
+    fn build(names: [&'static str; 3], fail_at: Option<usize>) -> [DropSpy; 3] {
+        let mut slots = [const { MaybeUninit::<DropSpy>::uninit() }; 3];
+        let mut guard = Guard {
+            slots: &mut slots,
+            initialized: 0,
+        };
+        for (i, name) in names.into_iter().enumerate() {
+            if fail_at == Some(i) {
+                panic!("failed to build element {}", i);
+            }
+            guard.slots[i].write(DropSpy(name));
+            guard.initialized += 1;
+        }
+        std::mem::forget(guard);
+        // Safety: all three elements were written
+        slots.map(|slot| unsafe { slot.assume_init() })
+    }
+
+    let result = panic::catch_unwind(|| build(["a", "b", "c"], Some(2)));
+    println!("building failed: {}", result.is_err());
+    let built = build(["x", "y", "z"], None);
+    println!("built {}, {} and {}", built[0].0, built[1].0, built[2].0);
+}
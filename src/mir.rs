@@ -0,0 +1,223 @@
+// Show the drops the compiler inserted into an example. The example is compiled on its own with
+// `rustc --emit=mir`, without MIR optimizations so that the locals keep their names, and with the
+// source span of every statement. An explicit `drop(x)` is an ordinary function call, so it
+// doesn't show up here. Nor does a type without drop glue, which is nothing to drop.
+//
+// The `-Z` flags for that only work on a nightly rustc, whichever toolchain `dm` was built with:
+// `RUSTUP_TOOLCHAIN=nightly cargo run -- mir drop_order`, or set RUSTC to a nightly rustc.
+//
+// - `mir` prints the drop terminators in the order of the basic blocks. The blocks marked as
+//   cleanup only run while unwinding from a panic. A drop that is only reached when a drop flag
+//   is set tells which flag, see the drop_flags lesson.
+// - `elaborate` prints the source of the example with the drops and the ends of storage under the
+//   line they belong to.
+
+use crate::scratch::Scratch;
+use dm::Example;
+use std::{env, fs, io, process::Command};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Drop,
+    StorageDead,
+}
+
+pub struct Point {
+    pub block: String,
+    pub cleanup: bool,
+    pub action: Action,
+    pub local: String,
+    // The variable in the source, if the local is one
+    pub name: Option<String>,
+    pub ty: String,
+    // In the program given to rustc, see `Example::program`
+    pub line: Option<usize>,
+    // The drop flag checked before getting to a drop, if any
+    pub flag: Option<String>,
+}
+
+impl Point {
+    fn local(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} ({})", self.local, name),
+            None => self.local.clone(),
+        }
+    }
+}
+
+// Compile the example on its own and return its MIR
+pub fn emit(example: &Example) -> io::Result<String> {
+    let dir = Scratch::new(&format!("dm-mir-{}", example.name))?;
+    let (source, mir) = (dir.join("main.rs"), dir.join("main.mir"));
+    fs::write(&source, example.program("").map_err(io::Error::other)?)?;
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "--crate-type", "bin", "--emit=mir"])
+        .args(["-Zmir-opt-level=0", "-Zmir-include-spans=yes", "-o"])
+        .arg(&mir)
+        .arg(&source)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("only accepted on the nightly compiler") {
+            let hint = format!(
+                "the MIR needs a nightly rustc, try `RUSTUP_TOOLCHAIN=nightly cargo run -- mir {}`",
+                example.name
+            );
+            return Err(io::Error::other(hint));
+        }
+        return Err(io::Error::other(format!("rustc failed:\n{}", stderr)));
+    }
+    fs::read_to_string(mir)
+}
+
+// The drops and ends of storage in the body of `fn name()`
+pub fn points(mir: &str, name: &str) -> Vec<Point> {
+    let signature = format!("fn {}() -> () {{", name);
+    let body = mir
+        .lines()
+        .skip_while(|line| !line.starts_with(&signature))
+        .take_while(|line| *line != "}");
+    let (mut types, mut names, mut points) = (Vec::new(), Vec::new(), Vec::new());
+    // The blocks that only run when a boolean is true, with that boolean
+    let mut guarded = Vec::new();
+    let (mut block, mut cleanup) = (String::new(), false);
+    for line in body {
+        // drop(_1) -> [return: bb2, unwind continue]; // scope 0 at main.rs:18:1: 18:2
+        let (line, span) = line.split_once("//").unwrap_or((line, ""));
+        let line = line.trim();
+        let span = span
+            .split_once("main.rs:")
+            .and_then(|(_, position)| position.split(':').next())
+            .and_then(|line| line.parse().ok());
+        let statement = |prefix| {
+            line.strip_prefix(prefix)
+                .and_then(|rest: &str| rest.split(')').next())
+        };
+        // let _1: drop_glue2::B1;
+        if let Some((local, ty)) = line
+            .strip_prefix("let mut ")
+            .or(line.strip_prefix("let "))
+            .and_then(|declaration| declaration.split_once(": "))
+        {
+            types.push((local.to_owned(), ty.trim_end_matches(';').to_owned()));
+        // debug b1 => _1;
+        } else if let Some((name, local)) = line
+            .strip_prefix("debug ")
+            .and_then(|debug| debug.split_once(" => "))
+        {
+            names.push((local.trim_end_matches(';').to_owned(), name.to_owned()));
+        // bb3 (cleanup): {
+        } else if line.starts_with("bb") && line.ends_with('{') {
+            block = line.split([' ', ':']).next().unwrap_or("").to_owned();
+            cleanup = line.contains("(cleanup)");
+        // switchInt(copy _13) -> [0: bb12, otherwise: bb15];
+        } else if let Some((flag, targets)) = line
+            .strip_prefix("switchInt(copy ")
+            .and_then(|rest| rest.split_once(") -> [0: "))
+        {
+            let otherwise = targets
+                .split_once(", otherwise: ")
+                .map(|(_, target)| target);
+            if let Some(target) = otherwise.and_then(|target| target.split(']').next()) {
+                guarded.push((target.to_owned(), flag.to_owned()));
+            }
+        } else if let Some((action, local)) = statement("drop(")
+            .map(|local| (Action::Drop, local))
+            .or(statement("StorageDead(").map(|local| (Action::StorageDead, local)))
+        {
+            let lookup = |table: &[(String, String)]| {
+                table
+                    .iter()
+                    .find(|(key, _)| key == local)
+                    .map(|(_, value)| value.clone())
+            };
+            points.push(Point {
+                block: block.clone(),
+                cleanup,
+                action,
+                local: local.to_owned(),
+                name: lookup(&names),
+                ty: lookup(&types).unwrap_or_default(),
+                line: span,
+                flag: None,
+            });
+        }
+    }
+    // Only the flags the compiler made up: they are booleans without a name in the source
+    for point in &mut points {
+        point.flag = guarded
+            .iter()
+            .find(|(target, flag)| {
+                *target == point.block
+                    && !names.iter().any(|(local, _)| local == flag)
+                    && types
+                        .iter()
+                        .any(|(local, ty)| local == flag && ty == "bool")
+            })
+            .map(|(_, flag)| flag.clone());
+    }
+    points
+}
+
+pub fn run(example: &Example) -> io::Result<()> {
+    let points = points(&emit(example)?, example.name);
+    let drops: Vec<_> = points.iter().filter(|p| p.action == Action::Drop).collect();
+    println!("drops inserted into {}() by the compiler:", example.name);
+    if drops.is_empty() {
+        println!("  none");
+    }
+    for drop in drops {
+        let when = if drop.cleanup {
+            "  [unwinding only]"
+        } else {
+            ""
+        };
+        let flag = match &drop.flag {
+            Some(flag) => format!("  [if drop flag {} is set]", flag),
+            None => String::new(),
+        };
+        println!(
+            "  {:5} drop {}: {}{}{}",
+            drop.block,
+            drop.local(),
+            drop.ty,
+            flag,
+            when
+        );
+    }
+    Ok(())
+}
+
+// The source of the example with the drop elaboration interleaved. The ends of storage of
+// temporaries are left out, there are too many of them to read the rest.
+pub fn elaborate(example: &Example) -> io::Result<()> {
+    let points = points(&emit(example)?, example.name);
+    let program = example.program("").map_err(io::Error::other)?;
+    let signature = format!("fn {}() {{", example.name);
+    let lines = program.lines().enumerate().map(|(i, line)| (i + 1, line));
+    let mut body = lines.skip_while(|(_, line)| *line != signature);
+    for (number, line) in body.by_ref() {
+        println!("{:4} | {}", number, line);
+        let indent = line.len() - line.trim_start().len();
+        for point in points.iter().filter(|point| point.line == Some(number)) {
+            let text = match point.action {
+                Action::Drop => format!("drop {}: {}", point.local(), point.ty),
+                Action::StorageDead if point.name.is_some() => {
+                    format!("StorageDead {}", point.local())
+                }
+                Action::StorageDead => continue,
+            };
+            let when = if point.cleanup {
+                " [unwinding only]"
+            } else {
+                ""
+            };
+            println!("     | {:indent$}^ {}{}", "", text, when);
+        }
+        if line == "}" {
+            break;
+        }
+    }
+    Ok(())
+}
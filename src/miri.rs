@@ -0,0 +1,52 @@
+// Run an example under Miri, the interpreter that detects undefined behavior. may_dangle5 and
+// phantom2 read memory that has already been freed. They usually print something plausible
+// anyway, which is exactly what makes undefined behavior so nasty. Miri tells it for sure. set_len2
+// only frees a String twice under Miri, a normal run stops short of it.
+//
+// The example runs on its own in a scratch cargo project, since Miri can't interpret jemalloc.
+// Miri is a rustup component: `rustup +nightly component add miri`.
+
+use crate::scratch::Scratch;
+use dm::Example;
+use std::{env, fs, io, process::Command};
+
+// Fails with what Miri said, whether it found undefined behavior or failed otherwise
+pub fn run(example: &Example) -> io::Result<()> {
+    let dir = Scratch::new(&format!("dm-miri-{}", example.name))?;
+    fs::create_dir_all(dir.join("src"))?;
+    let manifest = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n[workspace]\n",
+        example.name
+    );
+    fs::write(dir.join("Cargo.toml"), manifest)?;
+    let program = example.program("").map_err(io::Error::other)?;
+    fs::write(dir.join("src/main.rs"), program)?;
+
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let installed = Command::new(&cargo).args(["miri", "--version"]).output()?;
+    if !installed.status.success() {
+        let hint = "Miri is not installed, try `rustup +nightly component add miri`";
+        return Err(io::Error::other(hint));
+    }
+    let output = Command::new(cargo)
+        .args(["miri", "run", "--quiet"])
+        .current_dir(&*dir)
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    if output.status.success() {
+        eprint!("{}", stderr);
+        println!("\nMiri found no undefined behavior in {}", example.name);
+        return Ok(());
+    }
+    let verdict = if stderr.contains("Undefined Behavior") {
+        format!("Miri found undefined behavior in {}", example.name)
+    } else {
+        format!("Miri failed on {}: {}", example.name, output.status)
+    };
+    Err(io::Error::other(format!(
+        "{}\n\n{}",
+        stderr.trim_end(),
+        verdict
+    )))
+}
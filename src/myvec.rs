@@ -0,0 +1,146 @@
+// The MyBox of the phantom lessons, grown into a Vec the way the
+// [Rustonomicon](https://doc.rust-lang.org/nomicon/vec/vec.html) builds one. It puts together what
+// the lessons teach one at a time:
+// - the buffer is a raw allocation, grown by hand, holding `len` initialized elements out of `cap`
+// - it owns its elements through PhantomData<T>, so the drop checker knows it drops T's
+// - so its Drop can be `#[may_dangle]`: a MyVec<&T> may outlive the T's it borrows, like a Vec
+// - the elements are dropped in place as a slice, then the buffer is freed by its allocator, which
+//   gets no `#[may_dangle]` since the Drop uses it
+//
+// `cargo +nightly miri test --test myvec --no-default-features --features nightly` runs the tests
+// under Miri, which catches an element dropped twice, or not at all, or a read out of bounds.
+
+use std::{
+    alloc::{self, Allocator, Global, Layout},
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+// Like the Unique<T> of the standard library: a pointer that is never null, covariant in T like a
+// Box<T>, and that owns what it points to as far as the drop checker is concerned
+struct Unique<T> {
+    ptr: NonNull<T>,
+    _owns: PhantomData<T>,
+}
+
+// A raw pointer is neither Send nor Sync, but this one is as much as the T's it owns
+unsafe impl<T: Send> Send for Unique<T> {}
+unsafe impl<T: Sync> Sync for Unique<T> {}
+
+// The buffer comes from A, the global allocator unless `new_in` is given another one, like the
+// `Vec<T, A: Allocator = Global>` of std
+pub struct MyVec<T, A: Allocator = Global> {
+    buf: Unique<T>,
+    cap: usize,
+    len: usize,
+    alloc: A,
+}
+
+impl<T> MyVec<T> {
+    pub fn new() -> MyVec<T> {
+        MyVec::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> MyVec<T, A> {
+    // A zero-sized T takes no room, so there is never anything to allocate and never a need to
+    // grow. The dangling pointer is as good as any other for those.
+    pub fn new_in(alloc: A) -> MyVec<T, A> {
+        let cap = if mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            0
+        };
+        MyVec {
+            buf: Unique {
+                ptr: NonNull::dangling(),
+                _owns: PhantomData,
+            },
+            cap,
+            len: 0,
+            alloc,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    // Double the capacity, starting from 1. The elements are moved by `grow` as plain bytes, which
+    // is fine: moving a value in Rust is a copy of its bytes and never runs any code.
+    fn grow(&mut self) {
+        assert!(mem::size_of::<T>() != 0, "capacity overflow");
+        let new_cap = if self.cap == 0 { 1 } else { 2 * self.cap };
+        let new_layout = Layout::array::<T>(new_cap).expect("capacity overflow");
+        assert!(
+            new_layout.size() <= isize::MAX as usize,
+            "allocation too large"
+        );
+        let new_ptr = if self.cap == 0 {
+            self.alloc.allocate(new_layout)
+        } else {
+            let old_layout = Layout::array::<T>(self.cap).unwrap();
+            let old_ptr = self.buf.ptr.cast::<u8>();
+            unsafe { self.alloc.grow(old_ptr, old_layout, new_layout) }
+        };
+        self.buf.ptr = match new_ptr {
+            Ok(ptr) => ptr.cast::<T>(),
+            Err(_) => alloc::handle_alloc_error(new_layout),
+        };
+        self.cap = new_cap;
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.len == self.cap {
+            self.grow();
+        }
+        // The slot is past the initialized elements, so there is nothing there to drop
+        unsafe { ptr::write(self.buf.ptr.as_ptr().add(self.len), value) };
+        self.len += 1;
+    }
+
+    // The element is read out and the length shrinks, so that the vector never sees it again.
+    // From now on, dropping it is up to the caller.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { ptr::read(self.buf.ptr.as_ptr().add(self.len)) })
+    }
+}
+
+impl<T> Default for MyVec<T> {
+    fn default() -> MyVec<T> {
+        MyVec::new()
+    }
+}
+
+impl<T, A: Allocator> Deref for MyVec<T, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.buf.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T, A: Allocator> DerefMut for MyVec<T, A> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.buf.ptr.as_ptr(), self.len) }
+    }
+}
+
+// The drop never looks at a T other than to drop it, which is what #[may_dangle] promises. That
+// promise only holds together with the PhantomData<T> in Unique: see phantom2 for what goes wrong
+// without it. A gets no eyepatch, the drop calls it to free the buffer, see phantom6.
+unsafe impl<#[may_dangle] T, A: Allocator> Drop for MyVec<T, A> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(&mut **self as *mut [T]) };
+        if self.cap != 0 && mem::size_of::<T>() != 0 {
+            let layout = Layout::array::<T>(self.cap).unwrap();
+            unsafe { self.alloc.deallocate(self.buf.ptr.cast(), layout) };
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use crate::tracker;
+use std::{
+    alloc::{self, Layout},
+    marker::PhantomData,
+    ptr,
+};
+
+// Everything seems perfect right now -- except only one issue left.
+// To see the subtle pitfall, let us write a Box-like struct ourselves.
+#[allow(unused)]
+pub fn phantom1() {
+    struct MyBox<T>(*mut T); // T is not owned by MyVec because it's a pointer.
+    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+        fn drop(&mut self) {
+            tracker::dropped("MyBox");
+            // If T is a reference type, simply free all the allocated space. We don't have to
+            // bother with dropping them since they are borrowed instead of owned. That's why we
+            // are going to add #[may_dangle] for the same reason as shown above.
+            // ptr::drop_in_place would do nothing to a reference. That's fine. references does
+            // nothing except for dropping the memory to store those references.
+            println!("a dropped");
+            unsafe {
+                ptr::drop_in_place(self.0);
+                alloc::dealloc(self.0 as *mut u8, Layout::new::<T>())
+            };
+        }
+    }
+    impl<T> MyBox<T> {
+        fn new() -> MyBox<T> {
+            tracker::constructed("MyBox");
+            MyBox(unsafe { alloc::alloc(Layout::new::<T>()) } as *mut T)
+        }
+        fn move_in(&mut self, mut t: T) {
+            unsafe {
+                ptr::swap(self.0, &mut t as *mut T);
+            }
+        }
+    }
+
+    let mut a = MyBox::new();
+    let s = String::from("233");
+    a.move_in(&s);
+    drop(s);
+    println!("s dropped");
+    println!("&s dangles ever since");
+    // a get dropped here when &s is dangling
+}
+
+// On the other hand, if T is actually OWNED by MyBox like MyBox<T>, we must drop all of them
+// individually. So we really need to have mutable access to them and we hope our
+// compiler check works in such cases. However, #[may_dangle] skims through
+// the definition of struct MyVec<T> and say "T is not owned by MyVec. So I should take
+// effect", which makes the compiler ignores those checks.
+#[allow(unused)]
+pub fn phantom2() {
+    struct MyBox<T>(*mut T);
+    struct PrintOnDrop<'s>(&'s str);
+    impl<'s> Drop for PrintOnDrop<'s> {
+        fn drop(&mut self) {
+            tracker::dropped("PrintOnDrop");
+            println!("PrintOnDrop dropped as part of drop glue of MyBox");
+            println!("visit a dangling reference: {}", self.0);
+        }
+    }
+    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+    // impl<T> Drop for MyBox<T> { // If this line uncommented?
+        fn drop(&mut self) {
+            tracker::dropped("MyBox");
+            println!("MyBox dropped");
+            unsafe {
+                ptr::drop_in_place(self.0);
+                alloc::dealloc(self.0 as *mut u8, Layout::new::<T>())
+            };
+        }
+    }
+    impl<T> MyBox<T> {
+        fn new() -> MyBox<T> {
+            tracker::constructed("MyBox");
+            MyBox(unsafe { alloc::alloc(Layout::new::<T>()) } as *mut T)
+        }
+        fn move_in(&mut self, mut t: T) {
+            unsafe {
+                std::mem::forget(std::mem::replace(&mut *self.0, t));
+            }
+        }
+    }
+
+    let mut a = MyBox::new();
+    let mut s = "Hello".to_owned();
+    s.push_str(" world");
+    a.move_in(PrintOnDrop(s.as_str()));
+    drop(s);
+    println!("s dropped");
+    // MyBox dropped here
+    // run the code to see the output. Then run `cargo run -- miri phantom2` to see what Miri
+    // thinks of it.
+}
+
+// The point to resolve this trouble is to make T owned by MyVec in some way. Something tricky like
+// a zero-sized array in C language might have it settled but in rust we have a dedicated type,
+// PhantomData<T>, for this. What is
+// [PhantomData](https://doc.rust-lang.org/std/marker/struct.PhantomData.html)?
+#[allow(unused)]
+pub fn phantom3() {
+    struct MyBox<T>(*mut T, PhantomData<T>);
+    struct PrintOnDrop<'s>(&'s str);
+    impl<'s> Drop for PrintOnDrop<'s> {
+        fn drop(&mut self) {
+            tracker::dropped("PrintOnDrop");
+            println!("PrintOnDrop dropped as part of drop glue of MyBox");
+            println!("visit a dangling reference: {}", self.0);
+        }
+    }
+    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+        fn drop(&mut self) {
+            tracker::dropped("MyBox");
+            println!("MyBox dropped");
+            unsafe {
+                ptr::drop_in_place(self.0);
+                alloc::dealloc(self.0 as *mut u8, Layout::new::<T>())
+            };
+        }
+    }
+    impl<T> MyBox<T> {
+        fn new() -> MyBox<T> {
+            tracker::constructed("MyBox");
+            MyBox(
+                unsafe { alloc::alloc(Layout::new::<T>()) } as *mut T,
+                PhantomData,
+            )
+        }
+        fn move_in(&mut self, mut t: T) {
+            unsafe {
+                std::mem::forget(std::mem::replace(&mut *self.0, t));
+            }
+        }
+    }
+
+    // uncomment the following lines to see what happens; or read tests/ui/phantom3.stderr
+    // let mut a = MyBox::new();
+    // let mut s = "Hello".to_owned();
+    // s.push_str(" world");
+    // a.move_in(PrintOnDrop(s.as_str()));
+    // drop(s);
+    // println!("s dropped");
+}
+
+// MyBox drops its one T with `ptr::drop_in_place(self.0)`. A MyVec would own a whole run of T's,
+// and the same function drops them all at once given a pointer to a slice. `*mut [T]` is a fat
+// pointer: the address and the length. The drop glue of a slice reads the length and drops the
+// elements one by one, first to last. A `*mut dyn Trait` is fat too, with a vtable instead of a
+// length, and its drop glue is looked up in there. For a sized T there is no metadata at all: the
+// type alone tells what to drop. Either way, the memory itself is not freed, that's dealloc's job.
+#[allow(unused)]
+pub fn phantom4() {
+    struct PrintOnDrop(&'static str);
+    impl Drop for PrintOnDrop {
+        fn drop(&mut self) {
+            tracker::dropped(self.0);
+            println!("{} dropped", self.0);
+        }
+    }
+
+    let names = ["first", "second", "third"];
+    let layout = Layout::array::<PrintOnDrop>(names.len()).unwrap();
+    let start = unsafe { alloc::alloc(layout) } as *mut PrintOnDrop;
+    for (i, name) in names.into_iter().enumerate() {
+        unsafe { start.add(i).write(PrintOnDrop(name)) };
+    }
+    let slice: *mut [PrintOnDrop] = ptr::slice_from_raw_parts_mut(start, names.len());
+    println!("a *mut [PrintOnDrop] of {} elements", slice.len());
+    unsafe {
+        ptr::drop_in_place(slice);
+        println!("all of them dropped, the memory is still there");
+        alloc::dealloc(start as *mut u8, layout);
+    }
+
+    let boxed: Box<dyn Fn()> = Box::new({
+        let captured = PrintOnDrop("captured");
+        move || println!("{}", captured.0)
+    });
+    let raw: *mut dyn Fn() = Box::into_raw(boxed);
+    // The size and alignment are in the vtable too
+    let layout = Layout::for_value(unsafe { &*raw });
+    unsafe {
+        ptr::drop_in_place(raw);
+        println!("the closure behind a *mut dyn Fn() dropped, through its vtable");
+        alloc::dealloc(raw as *mut u8, layout);
+    }
+}
+
+// Three ways to hold the pointer of a Box-like type, and what each tells the compiler:
+// - `*mut T` owns nothing and is invariant in T: a RawBox<&'static str> is not a RawBox<&'a str>,
+//   even though a &'static str is a &'a str. The compiler can't rule out writes through the
+//   pointer, and writing a short-lived &str where a &'static str is expected would be unsound.
+// - `NonNull<T>` is a `*const T` that is never null, so it is covariant in T like a reference or
+//   a Box. It is up to us not to write through it in a way that breaks covariance.
+// - a `Unique<T>`, NonNull<T> plus PhantomData<T>, owns a T on top of that. That's how the standard
+//   library builds Box and Vec: covariant, and able to put #[may_dangle] on their Drop, since the
+//   drop checker still knows they drop a T.
+// UniqueBox<&str> below is dropped after the String it borrows, just like a Box<&str> may be.
+#[allow(unused, clippy::needless_late_init)]
+pub fn phantom5() {
+    struct RawBox<T>(*mut T);
+    struct NonNullBox<T>(ptr::NonNull<T>);
+    struct UniqueBox<T>(ptr::NonNull<T>, PhantomData<T>);
+    unsafe impl<#[may_dangle] T> Drop for UniqueBox<T> {
+        fn drop(&mut self) {
+            tracker::dropped("UniqueBox");
+            println!("UniqueBox dropped");
+            unsafe { drop(Box::from_raw(self.0.as_ptr())) };
+        }
+    }
+    impl<T> UniqueBox<T> {
+        fn new(value: T) -> UniqueBox<T> {
+            let raw = Box::into_raw(Box::new(value));
+            UniqueBox(unsafe { ptr::NonNull::new_unchecked(raw) }, PhantomData)
+        }
+    }
+
+    fn shorten<'a>(b: NonNullBox<&'static str>) -> NonNullBox<&'a str> {
+        b
+    }
+    fn shorten_unique<'a>(b: UniqueBox<&'static str>) -> UniqueBox<&'a str> {
+        b
+    }
+    // uncomment the following three lines to see the error (or read tests/ui/phantom5.stderr)
+    // fn shorten_raw<'a>(b: RawBox<&'static str>) -> RawBox<&'a str> {
+    //     b
+    // }
+
+    let shortened = shorten_unique(UniqueBox::new("static"));
+    let boxed;
+    let s = String::from("borrowed");
+    boxed = UniqueBox::new(s.as_str());
+    println!("a UniqueBox<&str> may outlive what it borrows");
+    // s gets dropped here
+    // boxed gets dropped here, &s dangles but nobody looks at it
+    // shortened gets dropped here
+}
+
+// The standard library spells the same pattern out with one more parameter, the allocator:
+//
+//   unsafe impl<#[may_dangle] T: ?Sized, A: Allocator> Drop for Box<T, A>
+//   unsafe impl<#[may_dangle] T, A: Allocator> Drop for Vec<T, A>
+//
+// T gets the eyepatch and A doesn't. The Drop drops the T's without looking at them, which is what
+// #[may_dangle] promises, but it calls the allocator to give the memory back, so the allocator has
+// to be alive when the box is dropped. The MyBox below takes an allocator the same way, as does the
+// MyVec of src/myvec.rs, one that borrows a counter of live allocations: the &String it holds may
+// dangle when it is dropped, the counter may not. `Allocator` is unstable, like #[may_dangle].
+#[allow(unused, clippy::needless_late_init)]
+pub fn phantom6() {
+    use std::{
+        alloc::{AllocError, Allocator, Global},
+        cell::Cell,
+        ptr::NonNull,
+    };
+    struct Counting<'c>(&'c Cell<usize>);
+    unsafe impl Allocator for Counting<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+    struct MyBox<T, A: Allocator = Global> {
+        ptr: NonNull<T>,
+        alloc: A,
+        _owns: PhantomData<T>,
+    }
+    impl<T, A: Allocator> MyBox<T, A> {
+        fn new_in(value: T, alloc: A) -> MyBox<T, A> {
+            let ptr = alloc.allocate(Layout::new::<T>()).expect("out of memory");
+            let ptr = ptr.cast::<T>();
+            unsafe { ptr.as_ptr().write(value) };
+            MyBox {
+                ptr,
+                alloc,
+                _owns: PhantomData,
+            }
+        }
+    }
+    unsafe impl<#[may_dangle] T, A: Allocator> Drop for MyBox<T, A> {
+        fn drop(&mut self) {
+            tracker::dropped("MyBox");
+            unsafe {
+                ptr::drop_in_place(self.ptr.as_ptr());
+                self.alloc.deallocate(self.ptr.cast(), Layout::new::<T>());
+            }
+            println!("MyBox dropped, its allocator still counts");
+        }
+    }
+
+    let live = Cell::new(0);
+    let boxed;
+    let s = String::from("borrowed");
+    boxed = MyBox::new_in(&s, Counting(&live));
+    println!("{} live allocation", live.get());
+    // uncomment the following three lines to see the error (or read tests/ui/phantom6.stderr)
+    // let late;
+    // let late_live = Cell::new(0);
+    // late = MyBox::new_in(1, Counting(&late_live));
+    // s gets dropped here
+    // boxed gets dropped here, &s dangles but nobody looks at it, live is still there
+}
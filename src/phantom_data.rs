@@ -0,0 +1,182 @@
+use crate::{assert_drops, tracker, util::DropCounter};
+use std::{marker::PhantomData, ptr::NonNull};
+
+// phantom3 settles on PhantomData<T>, but the type inside PhantomData can be anything, and each
+// choice tells the compiler something different about the raw pointer next to it:
+//
+//   PhantomData<T>              owns a T     covariant in T    Send/Sync if T is
+//   PhantomData<&'a T>          borrows a T  covariant in T    Send/Sync if T is Sync
+//   PhantomData<*const T>       nothing      covariant in T    never Send nor Sync
+//   PhantomData<fn(T) -> T>     nothing      invariant in T    always Send and Sync
+//
+// Owning only matters together with #[may_dangle]: the drop checker then knows that the Drop may
+// still drop a T, so a T that borrows something must not outlive it. The examples below take them
+// one at a time.
+
+// PhantomData<T> is what a box that owns its T should use. The drop checker rejects a PrintOnDrop
+// that borrows a String declared after the box, since dropping the box drops the PrintOnDrop,
+// which reads the String. And the box is covariant like Box: a MyBox<&'static str> can be used
+// where a MyBox<&'a str> is expected.
+#[allow(unused)]
+pub fn phantom_data1() {
+    struct MyBox<T>(NonNull<T>, PhantomData<T>);
+    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+        fn drop(&mut self) {
+            tracker::dropped("MyBox");
+            println!("MyBox dropped");
+            unsafe { drop(Box::from_raw(self.0.as_ptr())) };
+        }
+    }
+    impl<T> MyBox<T> {
+        fn new(value: T) -> MyBox<T> {
+            MyBox(NonNull::from(Box::leak(Box::new(value))), PhantomData)
+        }
+    }
+    struct PrintOnDrop<'s>(&'s str);
+    impl Drop for PrintOnDrop<'_> {
+        fn drop(&mut self) {
+            tracker::dropped("PrintOnDrop");
+            println!("PrintOnDrop dropped, it still reads {}", self.0);
+        }
+    }
+
+    fn shorten<'a>(b: MyBox<&'static str>) -> MyBox<&'a str> {
+        b
+    }
+
+    let s = String::from("s");
+    let boxed = MyBox::new(PrintOnDrop(&s));
+    let shortened = shorten(MyBox::new("static"));
+    // uncomment the following three lines to see the error (or read tests/ui/phantom_data1.stderr)
+    // let late;
+    // let t = String::from("t");
+    // late = MyBox::new(PrintOnDrop(&t));
+}
+
+// PhantomData<&'a T> claims to borrow a T, not to own it. That's right for a view into someone
+// else's data, like a slice iterator, and wrong for a box: the drop checker no longer knows that
+// the Drop drops a T, so the program left out of phantom_data1 would compile and read a String
+// that is already gone. The compiler can't tell, it's up to us to pick the right PhantomData.
+#[allow(unused)]
+pub fn phantom_data2() {
+    struct Iter<'a, T> {
+        next: *const T,
+        end: *const T,
+        _borrows: PhantomData<&'a T>,
+    }
+    impl<'a, T> Iter<'a, T> {
+        fn new(slice: &'a [T]) -> Iter<'a, T> {
+            let range = slice.as_ptr_range();
+            Iter {
+                next: range.start,
+                end: range.end,
+                _borrows: PhantomData,
+            }
+        }
+    }
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+        fn next(&mut self) -> Option<&'a T> {
+            if self.next == self.end {
+                return None;
+            }
+            // Safety: between the start and the end of a slice that outlives 'a
+            let item = unsafe { &*self.next };
+            self.next = unsafe { self.next.add(1) };
+            Some(item)
+        }
+    }
+
+    let names = [String::from("a"), String::from("b")];
+    let joined: Vec<&str> = Iter::new(&names).map(String::as_str).collect();
+    println!("the iterator borrowed {}", joined.join(" and "));
+}
+
+// PhantomData<*const T> neither owns nor borrows, and a raw pointer is neither Send nor Sync, so
+// the type isn't either. That's the way to opt out of both for a handle that must stay on the
+// thread that created it. A MyBox marked like this could not be sent to another thread even with
+// a T that can be.
+#[allow(unused)]
+pub fn phantom_data3() {
+    struct Handle<T> {
+        id: u32,
+        _not_send: PhantomData<*const T>,
+    }
+    impl<T> Drop for Handle<T> {
+        fn drop(&mut self) {
+            tracker::dropped("Handle");
+            println!("Handle {} dropped on the thread that created it", self.id);
+        }
+    }
+
+    let handle = Handle::<String> {
+        id: 1,
+        _not_send: PhantomData,
+    };
+    // uncomment the following line to see the error (or read tests/ui/phantom_data3.stderr)
+    // std::thread::spawn(move || drop(handle));
+}
+
+// PhantomData<fn(T) -> T> owns nothing and is always Send and Sync, like a function pointer, even
+// with a T that isn't. But T appears both as an argument and as a result, which makes the type
+// invariant in T: a lifetime in T can neither be shortened nor lengthened. That's what a type that
+// both reads and writes T's needs, like Cell<T>, or a marker that must keep a lifetime exact.
+#[allow(unused)]
+pub fn phantom_data4() {
+    use std::{rc::Rc, thread};
+
+    struct Marker<T> {
+        id: u32,
+        _invariant: PhantomData<fn(T) -> T>,
+    }
+
+    // Rc isn't Send, yet the marker is
+    let marker = Marker::<Rc<u8>> {
+        id: 4,
+        _invariant: PhantomData,
+    };
+    // Moved as a whole, not only its id
+    let id = thread::spawn(move || {
+        let marker = marker;
+        marker.id
+    })
+    .join()
+    .unwrap();
+    println!("Marker {} went to another thread and back", id);
+    // uncomment the following three lines to see the error (or read tests/ui/phantom_data4.stderr)
+    // fn shorten<'a>(marker: Marker<&'static str>) -> Marker<&'a str> {
+    //     marker
+    // }
+}
+
+// Whether a Drop drops what it owns, and only once, doesn't show in the output when what it owns
+// prints nothing, like a String. Count instead: a DropCounter hands out tokens that count their
+// drops, and `assert_drops!(n, { ... })` panics unless the block dropped n of them. The MyBox of
+// phantom_data1 passes. Take the `drop(Box::from_raw(..))` out of its Drop and the first assertion
+// fails, the token is leaked.
+#[allow(unused)]
+pub fn phantom_data5() {
+    struct MyBox<T>(NonNull<T>, PhantomData<T>);
+    unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+        fn drop(&mut self) {
+            tracker::dropped("MyBox");
+            unsafe { drop(Box::from_raw(self.0.as_ptr())) };
+        }
+    }
+    impl<T> MyBox<T> {
+        fn new(value: T) -> MyBox<T> {
+            MyBox(NonNull::from(Box::leak(Box::new(value))), PhantomData)
+        }
+    }
+
+    let counter = DropCounter::new();
+    assert_drops!(1, {
+        let boxed = MyBox::new(counter.token());
+    });
+    // Moved out of the block, so not dropped at its end
+    let boxes = assert_drops!(0, {
+        [MyBox::new(counter.token()), MyBox::new(counter.token())]
+    });
+    assert_drops!(2, { drop(boxes) });
+    println!("{} tokens dropped, one per MyBox", counter.dropped());
+}
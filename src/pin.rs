@@ -0,0 +1,106 @@
+use crate::tracker;
+use std::{cell::RefCell, marker::PhantomPinned, mem::ManuallyDrop, pin::Pin, ptr};
+
+// Pinning promises more than "it won't move": the memory of a pinned value must not be reused or
+// freed before its destructor has run. That's what lets a pinned value hand out its own address,
+// e.g. to a list that points to it, and take it back in its Drop. Box::pin and pin! keep the
+// promise for us, but Pin::new_unchecked is unsafe because it doesn't: here, a value pinned inside
+// a ManuallyDrop is overwritten without being dropped. The list still has its address, which now
+// holds something else. Following the pointer would read b and think it is a.
+#[allow(unused)]
+pub fn pin1() {
+    thread_local! {
+        static LISTED: RefCell<Vec<(&'static str, usize)>> = const { RefCell::new(Vec::new()) };
+    }
+    struct Listed {
+        name: &'static str,
+        _pinned: PhantomPinned,
+    }
+    impl Listed {
+        fn list(self: Pin<&mut Self>) {
+            let address = &*self as *const Listed as usize;
+            LISTED.with(|listed| listed.borrow_mut().push((self.name, address)));
+        }
+    }
+    impl Drop for Listed {
+        fn drop(&mut self) {
+            tracker::dropped(self.name);
+            let address = self as *const Listed as usize;
+            LISTED.with(|listed| listed.borrow_mut().retain(|&(_, a)| a != address));
+            println!("{} dropped and taken off the list", self.name);
+        }
+    }
+    let listed = || LISTED.with(|listed| listed.borrow().clone());
+
+    let mut kept = Box::pin(Listed {
+        name: "kept",
+        _pinned: PhantomPinned,
+    });
+    kept.as_mut().list();
+    drop(kept);
+    println!(
+        "after dropping kept, the list has {} entries",
+        listed().len()
+    );
+
+    let mut slot = Box::new(ManuallyDrop::new(Listed {
+        name: "a",
+        _pinned: PhantomPinned,
+    }));
+    // Safety: NOT upheld, the value will be overwritten without being dropped
+    unsafe { Pin::new_unchecked(&mut **slot) }.list();
+    *slot = ManuallyDrop::new(Listed {
+        name: "b",
+        _pinned: PhantomPinned,
+    });
+    let (name, address) = listed()[0];
+    let reused = address == &**slot as *const Listed as usize;
+    println!(
+        "the list still has {}, at an address that now holds b: {}",
+        name, reused
+    );
+    // Clean up by hand, so that neither the entry nor the list outlive the example
+    LISTED.with(|listed| listed.take());
+    unsafe { ManuallyDrop::drop(&mut slot) };
+}
+
+// A type that is !Unpin can only be used through Pin<&mut Self>, but `Drop::drop` still takes a
+// plain &mut self, since it's the same trait for every type. The way out, straight from the docs
+// of std::pin, is to treat the &mut self as pinned right away: wrap it with Pin::new_unchecked
+// and do all the work in a function that takes Pin<&mut Self>. It's sound since the value is never
+// moved again: it is being dropped where it was pinned.
+#[allow(unused)]
+pub fn pin2() {
+    struct Task {
+        name: &'static str,
+        this: *const Task,
+        _pinned: PhantomPinned,
+    }
+    impl Task {
+        fn start(self: Pin<&mut Self>) {
+            let this = &*self as *const Task;
+            // Safety: only a field is set, the value is not moved out
+            unsafe { self.get_unchecked_mut().this = this };
+        }
+        fn pinned_drop(self: Pin<&mut Self>) {
+            let still_there = ptr::eq(self.this, &*self);
+            println!("{} dropped in place: {}", self.name, still_there);
+        }
+    }
+    impl Drop for Task {
+        fn drop(&mut self) {
+            tracker::dropped(self.name);
+            // Safety: the value is dropped where it was pinned and never used after this
+            unsafe { Pin::new_unchecked(self) }.pinned_drop();
+        }
+    }
+
+    let mut task = std::pin::pin!(Task {
+        name: "task",
+        this: ptr::null(),
+        _pinned: PhantomPinned,
+    });
+    task.as_mut().start();
+    println!("{} started", task.name);
+    // task gets dropped here
+}
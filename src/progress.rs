@@ -0,0 +1,123 @@
+// How far the reader got, kept across runs in a small file: the examples they ran, from the
+// command line, the menu or a walkthrough, and the ones whose quiz they answered right. `list` and
+// the menu mark them, and `reset` starts over.
+//
+// The file is `.dm-progress` in the current directory, or wherever DM_PROGRESS points. It has a
+// line per step, e.g. `ran drop_glue3` or `quizzed drop_glue3`, so it can be edited by hand.
+// Progress is only kept for a reader at a terminal, or when DM_PROGRESS asks for it: a script or a
+// test that runs the examples with stdout piped neither records nor sees any.
+
+use dm::EXAMPLES;
+use std::{
+    collections::BTreeSet,
+    env, fs,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Step {
+    Ran,
+    Quizzed,
+}
+
+impl Step {
+    fn as_str(self) -> &'static str {
+        match self {
+            Step::Ran => "ran",
+            Step::Quizzed => "quizzed",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Progress {
+    ran: BTreeSet<String>,
+    quizzed: BTreeSet<String>,
+}
+
+fn path() -> PathBuf {
+    env::var_os("DM_PROGRESS").map_or_else(|| PathBuf::from(".dm-progress"), PathBuf::from)
+}
+
+fn kept() -> bool {
+    env::var_os("DM_PROGRESS").is_some() || io::stdout().is_terminal()
+}
+
+impl Progress {
+    // What was recorded so far, nothing if there is no file yet
+    pub fn load() -> Progress {
+        let mut progress = Progress::default();
+        if !kept() {
+            return progress;
+        }
+        let text = fs::read_to_string(path()).unwrap_or_default();
+        for line in text.lines() {
+            match line.split_once(' ') {
+                Some(("ran", name)) => progress.ran.insert(name.to_owned()),
+                Some(("quizzed", name)) => progress.quizzed.insert(name.to_owned()),
+                _ => false,
+            };
+        }
+        progress
+    }
+
+    // A check mark for an example that was run, a star for a quiz answered right
+    pub fn marks(&self, name: &str) -> String {
+        let ran = if self.ran.contains(name) { '✓' } else { ' ' };
+        let quizzed = if self.quizzed.contains(name) {
+            '★'
+        } else {
+            ' '
+        };
+        format!("{}{}", ran, quizzed)
+    }
+
+    pub fn summary(&self) -> String {
+        let ran = EXAMPLES
+            .iter()
+            .filter(|example| self.ran.contains(example.name))
+            .count();
+        format!(
+            "{} of {} examples run (✓), {} quizzes answered right (★)",
+            ran,
+            EXAMPLES.len(),
+            self.quizzed.len()
+        )
+    }
+}
+
+// Append the step unless it is there already. Progress is nice to have, so failing to save it
+// is only a warning.
+pub fn record(step: Step, name: &str) {
+    if !kept() {
+        return;
+    }
+    let progress = Progress::load();
+    let done = match step {
+        Step::Ran => &progress.ran,
+        Step::Quizzed => &progress.quizzed,
+    };
+    if done.contains(name) {
+        return;
+    }
+    let result = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path())
+        .and_then(|mut file| writeln!(file, "{} {}", step.as_str(), name));
+    if let Err(err) = result {
+        eprintln!(
+            "failed to save the progress to {}: {}",
+            path().display(),
+            err
+        );
+    }
+}
+
+pub fn reset() -> io::Result<()> {
+    match fs::remove_file(path()) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,206 @@
+// Active recall: show the code of an example, ask in which order its types are dropped, then run
+// it and grade the prediction. The types that can be asked about are the ones the example records
+// in the tracker, and the right answer is whatever the tracker saw while running it.
+//
+// The questions of the bank in quizzes/ about the same example come right after, see quizzes.rs.
+// DM_QUIZZES points to another directory of questions.
+
+use dm::{
+    quizzes::{self, Question},
+    tracker, Example, EXAMPLES,
+};
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+// The names passed to `tracker::dropped` in the code of the example, sorted so that the question
+// doesn't give the answer away
+fn candidates(code: &str) -> Vec<&str> {
+    let mut names: Vec<_> = code
+        .split("tracker::dropped(\"")
+        .skip(1)
+        .filter_map(|rest| rest.split('"').next())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn words(line: &str) -> Vec<String> {
+    line.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// Ask about one example. Returns whether the prediction was right, or None when there is nothing to
+// ask about. An example that aborts is left out: it runs in this process and would end the quiz.
+// So is one that is not built in, there is nothing to run but the message that tells so.
+fn ask(example: &Example, input: &mut impl BufRead) -> io::Result<Option<bool>> {
+    if example.needs().is_some() {
+        (example.run)();
+        return Ok(None);
+    }
+    let (_, code) = example.lesson();
+    let candidates = candidates(&code);
+    if candidates.len() < 2 || example.aborts {
+        return Ok(None);
+    }
+    println!("===== {} =====", example.name);
+    println!("{}\n", code);
+    print!(
+        "In what order are {} dropped? (`none` if nothing is) ",
+        candidates.join(", ")
+    );
+    let answer = read_answer(input)?;
+
+    println!("----- output -----");
+    tracker::take();
+    (example.run)();
+    let order: Vec<_> = tracker::take()
+        .into_iter()
+        .filter(|event| event.kind == tracker::Kind::Dropped)
+        .map(|event| event.name)
+        .collect();
+    println!("------------------");
+    let mut predicted = words(&answer);
+    predicted.retain(|word| word != "none");
+    let correct = predicted == words(&order.join(" "));
+    if correct {
+        println!("Correct!\n");
+    } else if order.is_empty() {
+        println!("Not quite, nothing is dropped\n");
+    } else {
+        println!("Not quite, the order is {}\n", order.join(", "));
+    }
+    Ok(Some(correct))
+}
+
+fn read_answer(input: &mut impl BufRead) -> io::Result<String> {
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(answer)
+}
+
+// Ask a question of the bank, and tell whether the answer was right
+fn ask_bank(question: &Question, input: &mut impl BufRead) -> io::Result<bool> {
+    let correct = match question {
+        Question::Order {
+            prompt,
+            items,
+            answer,
+            ..
+        } => {
+            print!("{} ({}) ", prompt, items.join(", "));
+            let predicted = words(&read_answer(input)?);
+            let correct = predicted == words(&answer.join(" "));
+            if !correct {
+                println!("Not quite, the order is {}", answer.join(", "));
+            }
+            correct
+        }
+        Question::Choice {
+            prompt,
+            choices,
+            answer,
+            ..
+        } => {
+            println!("{}", prompt);
+            for (i, choice) in choices.iter().enumerate() {
+                println!("  {}. {}", i + 1, choice);
+            }
+            print!("Your choice: ");
+            let picked = read_answer(input)?.trim().parse::<usize>().ok();
+            let correct = picked == Some(*answer);
+            if !correct {
+                println!("Not quite, it is {}. {}", answer, choices[answer - 1]);
+            }
+            correct
+        }
+    };
+    if correct {
+        println!("Correct!");
+    }
+    if let Some(explanation) = question.explanation() {
+        println!("{}", explanation);
+    }
+    println!();
+    Ok(correct)
+}
+
+fn bank() -> Vec<Question> {
+    let dir = env::var_os("DM_QUIZZES").map_or_else(
+        || PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/quizzes")),
+        PathBuf::from,
+    );
+    match quizzes::load(&dir) {
+        Ok(questions) => questions,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => {
+            eprintln!("the question bank is broken: {}", err);
+            std::process::exit(2);
+        }
+    }
+}
+
+// Quiz on the given example, or on every example that has something to ask about
+pub fn run(name: Option<&str>) {
+    let bank = bank();
+    let examples: Vec<_> = match name {
+        Some(name) => EXAMPLES
+            .iter()
+            .filter(|example| example.name == name)
+            .collect(),
+        None => EXAMPLES.iter().collect(),
+    };
+    let mut input = io::stdin().lock();
+    let (mut asked, mut correct) = (0, 0);
+    // No more answers, the ones given so far still count
+    let mut out_of_answers = false;
+    for example in examples {
+        let mut results = Vec::new();
+        match ask(example, &mut input) {
+            Ok(Some(right)) => results.push(right),
+            Ok(None) => {}
+            Err(_) => out_of_answers = true,
+        }
+        for question in bank
+            .iter()
+            .filter(|question| question.example() == example.name)
+        {
+            if out_of_answers {
+                break;
+            }
+            if results.is_empty() {
+                println!("===== {} =====", example.name);
+            }
+            match ask_bank(question, &mut input) {
+                Ok(right) => results.push(right),
+                Err(_) => out_of_answers = true,
+            }
+        }
+        asked += results.len();
+        correct += results.iter().filter(|right| **right).count();
+        if out_of_answers {
+            println!();
+            break;
+        }
+        if !results.is_empty() && results.iter().all(|right| *right) {
+            crate::progress::record(crate::progress::Step::Quizzed, example.name);
+        }
+    }
+    if asked == 0 {
+        if out_of_answers {
+            eprintln!("no answer given, nothing to grade");
+        } else {
+            eprintln!("nothing to ask about, try one of the drop_order or drop_glue examples");
+        }
+        std::process::exit(2);
+    }
+    println!("{} of {} predicted right", correct, asked);
+}
@@ -0,0 +1,126 @@
+// The question bank of the quiz, kept as TOML under quizzes/ so that questions can be added
+// without touching any Rust. Every file holds a list of `[[question]]` tables, each tied to an
+// example by its name, and of one of two kinds:
+//
+// [[question]]
+// example = "drop_order"
+// kind = "order"
+// prompt = "In what order are x, y and z dropped?"
+// items = ["x", "y", "z"]
+// answer = ["z", "y", "x"]
+//
+// [[question]]
+// example = "forget1"
+// kind = "choice"
+// prompt = "What does mem::forget skip?"
+// choices = ["the Drop impl", "the deallocation of the value's own memory"]
+// answer = 1
+//
+// The answer of a choice counts from 1, like the choices are shown. `explanation` is optional for
+// both kinds, and shown after the answer. The bank is checked as a whole when it is loaded: a
+// question about an unknown example, or whose answer is not among what it offers, is an error.
+
+use serde::Deserialize;
+use std::{fs, io, path::Path};
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+pub enum Question {
+    // Put the items in the order in which they are dropped
+    Order {
+        example: String,
+        prompt: String,
+        items: Vec<String>,
+        answer: Vec<String>,
+        explanation: Option<String>,
+    },
+    // Pick one of the choices
+    Choice {
+        example: String,
+        prompt: String,
+        choices: Vec<String>,
+        answer: usize,
+        explanation: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    question: Vec<Question>,
+}
+
+impl Question {
+    pub fn example(&self) -> &str {
+        match self {
+            Question::Order { example, .. } | Question::Choice { example, .. } => example,
+        }
+    }
+
+    pub fn explanation(&self) -> Option<&str> {
+        match self {
+            Question::Order { explanation, .. } | Question::Choice { explanation, .. } => {
+                explanation.as_deref()
+            }
+        }
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if crate::find(self.example()).is_none() {
+            return Err(format!("there is no example `{}`", self.example()));
+        }
+        match self {
+            Question::Order { items, answer, .. } => {
+                let (mut sorted_items, mut sorted_answer) = (items.clone(), answer.clone());
+                sorted_items.sort();
+                sorted_answer.sort();
+                if items.len() < 2 || sorted_items != sorted_answer {
+                    return Err("the answer must put all the items, two or more, in order".into());
+                }
+            }
+            Question::Choice {
+                choices, answer, ..
+            } => {
+                if choices.len() < 2 || !(1..=choices.len()).contains(answer) {
+                    return Err(format!(
+                        "the answer must be one of the choices, 1 to {}, of which there must be \
+                         two or more",
+                        choices.len()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// The questions of one file, `name` being only there for the errors
+pub fn parse(name: &str, text: &str) -> Result<Vec<Question>, String> {
+    let file: File = toml::from_str(text).map_err(|err| format!("{}: {}", name, err))?;
+    for (i, question) in file.question.iter().enumerate() {
+        question
+            .check()
+            .map_err(|err| format!("{}: question {}: {}", name, i + 1, err))?;
+    }
+    Ok(file.question)
+}
+
+// Every question of every .toml file in `dir`, file by file in the order of their names
+pub fn load(dir: &Path) -> io::Result<Vec<Question>> {
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "toml")
+        })
+        .collect();
+    paths.sort();
+    let mut questions = Vec::new();
+    for path in paths {
+        let text = fs::read_to_string(&path)?;
+        let name = path.display().to_string();
+        questions.extend(parse(&name, &text).map_err(io::Error::other)?);
+    }
+    Ok(questions)
+}
@@ -0,0 +1,340 @@
+// Every example is registered in lib.rs once with its name, the topic it belongs to and a one-line
+// summary of its annotation. The runner, `list`, the menu and the tests all walk the same
+// registry, so adding an example is a matter of writing the function and one more line in
+// `register_examples!`.
+
+// How much of the tour a reader should have behind them, e.g. to pick the examples for a class
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Intro,
+    Intermediate,
+    Advanced,
+}
+
+impl Difficulty {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Difficulty::Intro => "intro",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Advanced => "advanced",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        [
+            Difficulty::Intro,
+            Difficulty::Intermediate,
+            Difficulty::Advanced,
+        ]
+        .into_iter()
+        .find(|difficulty| difficulty.as_str() == name)
+    }
+}
+
+pub struct Example {
+    pub name: &'static str,
+    // The module the example lives in
+    pub topic: &'static str,
+    // Set for a whole topic in `register_examples!`
+    pub difficulty: Difficulty,
+    pub description: &'static str,
+    pub run: fn(),
+    // Takes the whole process down, so whoever runs it had better do it in a process of its own.
+    // Marked with `#[aborts]` in `register_examples!`.
+    pub aborts: bool,
+    // Panics on purpose or spawns a thread, which WebAssembly in a browser can't do, so the web
+    // build only shows it. Marked with `#[native]` in `register_examples!`.
+    pub native: bool,
+    // The source of the whole module, so that the lesson can be shown without the repository
+    pub(crate) source: &'static str,
+}
+
+impl Example {
+    // The feature that is off for its topic, if any. The example then only tells that it needs it.
+    pub fn needs(&self) -> Option<&'static str> {
+        crate::GATED
+            .iter()
+            .find(|(topic, _, on)| *topic == self.topic && !on)
+            .map(|(_, feature, _)| *feature)
+    }
+
+    pub fn source(&self) -> &'static str {
+        self.source
+    }
+
+    // Cut the annotation right above the function and the function itself out of the source of
+    // its module, so that the explanations only have to be written once. A translation of the
+    // annotation takes its place if there is one, see locale.rs.
+    pub fn lesson(&self) -> (String, String) {
+        let lines: Vec<_> = self.source.lines().collect();
+        let signature = format!("pub fn {}() {{", self.name);
+        let Some(start) = lines.iter().position(|line| *line == signature) else {
+            return (String::new(), String::new());
+        };
+        let end = start
+            + lines[start..]
+                .iter()
+                .position(|line| *line == "}")
+                .unwrap_or(0);
+        let mut top = start;
+        while top > 0 && (lines[top - 1].starts_with("#[") || lines[top - 1].is_empty()) {
+            top -= 1;
+        }
+        let mut prose = Vec::new();
+        while top > 0 && lines[top - 1].starts_with("//") {
+            top -= 1;
+            prose.push(lines[top].trim_start_matches('/').trim());
+        }
+        prose.reverse();
+        let prose = match crate::locale::translation(self.name) {
+            Some((_, translated)) => translated,
+            None => prose.join("\n"),
+        };
+        (prose, lines[start..=end].join("\n"))
+    }
+
+    // The one-line description in the current language, see locale.rs
+    pub fn summary(&self) -> &'static str {
+        crate::locale::translation(self.name).map_or(self.description, |(summary, _)| summary)
+    }
+
+    // The example as a program on its own, e.g. for the playground or to feed rustc. The tracker
+    // calls are left out since there is no `dm` there. Every line that is only there to make it
+    // compile starts with `hidden`, e.g. mdBook's `# `. An example that calls into another crate
+    // of the workspace, like temporaries5 into dm-2024, can't be one and gets the reason instead.
+    pub fn program(&self, hidden: &str) -> Result<String, String> {
+        let (_, code) = self.lesson();
+        if code.contains("dm_2024::") {
+            return Err(format!(
+                "{} calls into the dm-2024 crate, it can't be compiled on its own",
+                self.name
+            ));
+        }
+        let mut program = format!("{}#![allow(unused)]\n", hidden);
+        // What the code uses, not what the comments tell about, e.g. shared.rs on the Rc of std
+        let uses = |feature: &str| {
+            self.source
+                .lines()
+                .any(|line| !line.trim_start().starts_with("//") && line.contains(feature))
+        };
+        if uses("#[may_dangle]") {
+            program.push_str(&format!("{}#![feature(dropck_eyepatch)]\n", hidden));
+        }
+        if uses("Allocator for") {
+            program.push_str(&format!("{}#![feature(allocator_api)]\n", hidden));
+        }
+        if uses("#[coroutine]") {
+            let features = "coroutines, coroutine_trait, stmt_expr_attributes";
+            program.push_str(&format!("{}#![feature({})]\n", hidden, features));
+        }
+        for line in imports(self.source) {
+            program.push_str(&format!("{}{}\n", hidden, line));
+        }
+        for line in items(self.source) {
+            if !line.contains("tracker::") {
+                program.push_str(&format!("{}{}\n", hidden, line));
+            }
+        }
+        // The lints the example allows, e.g. let_underscore_lock which is denied by default
+        for line in attributes(self.source, self.name) {
+            program.push_str(&format!("{}{}\n", hidden, line));
+        }
+        for line in code.lines().filter(|line| !line.contains("tracker::")) {
+            program.push_str(line.strip_prefix("pub ").unwrap_or(line));
+            program.push('\n');
+        }
+        program.push_str(&format!("{}fn main() {{\n", hidden));
+        program.push_str(&format!("{}    {}();\n", hidden, self.name));
+        program.push_str(&format!("{}}}\n", hidden));
+        // The small types of util.rs come along with the lessons that use them, in a module of
+        // the same name for `assert_drops!` to find them under `$crate::util`
+        let uses_util = self
+            .source
+            .lines()
+            .any(|line| line.starts_with("use crate::") && line.contains("util"));
+        if uses_util {
+            program.push_str(&format!("{}use util::*;\n", hidden));
+            program.push_str(&format!("{}mod util {{\n", hidden));
+            let util = UTIL
+                .lines()
+                .filter(|line| !line.contains("tracker::") && !line.starts_with("use crate::"));
+            for line in util {
+                program.push_str(&format!("{}{}\n", hidden, line));
+            }
+            program.push_str(&format!("{}}}\n", hidden));
+        }
+        Ok(program)
+    }
+}
+
+const UTIL: &str = include_str!("util.rs");
+
+// What a module defines for all of its lessons, e.g. the ScopeGuard of guard.rs: the code above
+// the first lesson, without the `use` items and the comments
+fn items(source: &str) -> Vec<&str> {
+    let lines: Vec<_> = source.lines().collect();
+    let Some(mut end) = lines.iter().position(|line| line.starts_with("pub fn ")) else {
+        return Vec::new();
+    };
+    while end > 0 && (lines[end - 1].starts_with("#[") || lines[end - 1].starts_with("//")) {
+        end -= 1;
+    }
+    let mut items = Vec::new();
+    let mut in_use = false;
+    for &line in &lines[..end] {
+        in_use |= line.starts_with("use ");
+        if !in_use && !line.starts_with("//") && (!items.is_empty() || !line.is_empty()) {
+            items.push(line);
+        }
+        in_use &= !line.ends_with(';');
+    }
+    items
+}
+
+// The attributes right above `pub fn name()`
+fn attributes<'a>(source: &'a str, name: &str) -> Vec<&'a str> {
+    let signature = format!("pub fn {}() {{", name);
+    let lines: Vec<_> = source.lines().collect();
+    let Some(start) = lines.iter().position(|line| *line == signature) else {
+        return Vec::new();
+    };
+    let top = lines[..start]
+        .iter()
+        .rposition(|line| !line.starts_with("#["))
+        .map_or(0, |i| i + 1);
+    lines[top..start].to_vec()
+}
+
+// The `use` items of a module except the crate's own
+fn imports(source: &str) -> Vec<&str> {
+    let mut imports = Vec::new();
+    let mut lines = source.lines();
+    while let Some(mut line) = lines.next() {
+        if !line.starts_with("use ") || line.starts_with("use crate::") {
+            continue;
+        }
+        imports.push(line);
+        while !line.ends_with(';') {
+            line = lines.next().unwrap_or(";");
+            imports.push(line);
+        }
+    }
+    imports
+}
+
+// What an example runs instead when the topic it belongs to is behind a feature that is off, see
+// `Example::needs` for the binary to fail on it. Unused as long as all those features are on.
+#[allow(dead_code)]
+pub fn needs(name: &str, feature: &str) {
+    // Only `#[may_dangle]` needs more than the feature itself
+    let (also, toolchain) = match feature {
+        "nightly" => (" and a nightly toolchain", "+nightly "),
+        _ => ("", ""),
+    };
+    eprintln!(
+        "`{}` is not built in, it needs the `{}` feature{}",
+        name, feature, also
+    );
+    eprintln!(
+        "try `cargo {}run --features {} -- {}`",
+        toolchain, feature, name
+    );
+}
+
+// A topic behind a feature stays registered, so that it can still be listed and read. Its examples
+// only tell what they need until the feature is on.
+macro_rules! gate {
+    ($topic:ident { $($name:ident)* }) => {};
+    (#[cfg(feature = $feature:literal)] $topic:ident { $($name:ident)* }) => {
+        #[cfg(not(feature = $feature))]
+        mod $topic {
+            $(
+                pub fn $name() {
+                    $crate::registry::needs(stringify!($name), $feature);
+                }
+            )*
+        }
+    };
+}
+pub(crate) use gate;
+
+macro_rules! difficulty {
+    (intro) => {
+        $crate::registry::Difficulty::Intro
+    };
+    (intermediate) => {
+        $crate::registry::Difficulty::Intermediate
+    };
+    (advanced) => {
+        $crate::registry::Difficulty::Advanced
+    };
+}
+pub(crate) use difficulty;
+
+// Whether the first flag is among the others, e.g. `flagged!(aborts native aborts)` is true. A
+// flag that is neither `aborts` nor `native` doesn't match, so a typo doesn't compile.
+macro_rules! flagged {
+    ($flag:ident) => {
+        false
+    };
+    (aborts aborts $($rest:ident)*) => {
+        true
+    };
+    (native native $($rest:ident)*) => {
+        true
+    };
+    ($flag:ident aborts $($rest:ident)*) => {
+        $crate::registry::flagged!($flag $($rest)*)
+    };
+    ($flag:ident native $($rest:ident)*) => {
+        $crate::registry::flagged!($flag $($rest)*)
+    };
+}
+pub(crate) use flagged;
+
+// Expands to a `pub const EXAMPLES: &[Example]` in reading order. Examples are grouped by the
+// module they live in, which is also their topic, and named after the function they run. Every
+// topic has a difficulty, one of intro, intermediate and advanced:
+//
+// register_examples! {
+//     module (intro) {
+//         function_name: "description",
+//         #[native]
+//         another_function: "description",
+//     }
+// }
+//
+// The flags before an example, `#[aborts]` and `#[native]`, set the fields of the same name.
+macro_rules! register_examples {
+    ($(
+        $(#[cfg(feature = $feature:literal)])?
+        $topic:ident ($difficulty:ident) {
+            $($(#[$flag:ident])* $name:ident: $description:literal,)*
+        }
+    )*) => {
+        $(
+            $crate::registry::gate! {
+                $(#[cfg(feature = $feature)])? $topic { $($name)* }
+            }
+        )*
+
+        // The topics behind a feature, with the feature and whether it is on
+        pub(crate) const GATED: &[(&str, &str, bool)] = &[$($(
+            (stringify!($topic), $feature, cfg!(feature = $feature)),
+        )?)*];
+
+        pub const EXAMPLES: &[$crate::registry::Example] = &[$($(
+            $crate::registry::Example {
+                name: stringify!($name),
+                topic: stringify!($topic),
+                difficulty: $crate::registry::difficulty!($difficulty),
+                description: $description,
+                run: $topic::$name,
+                aborts: $crate::registry::flagged!(aborts $($flag)*),
+                native: $crate::registry::flagged!(native $($flag)*),
+                source: include_str!(concat!(stringify!($topic), ".rs")),
+            },
+        )*)*];
+    };
+}
+pub(crate) use register_examples;
@@ -0,0 +1,59 @@
+use crate::util::DropSpy;
+use std::mem;
+
+// Assigning to a variable that holds a value drops that value first, right at the assignment,
+// since nothing could reach it afterwards. `mem::replace` puts the new value in all the same, but
+// hands the old one back instead of dropping it: the caller owns it now, and drops it whenever it
+// goes out of scope, here at the end of the block.
+#[allow(unused)]
+pub fn replace1() {
+    let mut slot = DropSpy("first");
+    slot = DropSpy("second");
+    println!("assigned second");
+    let old = mem::replace(&mut slot, DropSpy("third"));
+    println!("replaced {} with {}", old.0, slot.0);
+    // old gets dropped here, that is second
+    // slot gets dropped here, holding third
+}
+
+// `mem::swap` exchanges two values in place. Both still have an owner afterwards, so nothing is
+// dropped: the values are only moved, and a move never runs any code. Each is dropped later, by
+// whoever ends up with it.
+#[allow(unused)]
+pub fn replace2() {
+    let mut left = DropSpy("a");
+    let mut right = DropSpy("b");
+    mem::swap(&mut left, &mut right);
+    println!("left holds {}, right holds {}", left.0, right.0);
+    // right gets dropped here, holding a
+    // left gets dropped here, holding b
+}
+
+// `mem::take` is a replace with the default value, and `Option::take` the same for an Option: the
+// value is moved out and None left behind. That's how a value is moved out of a field through a
+// &mut, e.g. in a Drop, where moving out of self is not allowed. What is left behind is dropped
+// with its owner as usual, and dropping None or an empty Vec does nothing.
+#[allow(unused)]
+pub fn replace3() {
+    struct Owner {
+        spy: Option<DropSpy>,
+        spies: Vec<DropSpy>,
+    }
+    impl Drop for Owner {
+        fn drop(&mut self) {
+            if let Some(spy) = self.spy.take() {
+                println!("Owner took {} out in its Drop", spy.0);
+                // spy gets dropped here, before the drop glue of Owner runs
+            }
+        }
+    }
+
+    let mut owner = Owner {
+        spy: Some(DropSpy("in the option")),
+        spies: vec![DropSpy("in the vec")],
+    };
+    let spies = mem::take(&mut owner.spies);
+    println!("took {} spies out of the vec", spies.len());
+    // spies gets dropped here
+    // owner gets dropped here, with only None and an empty Vec left in its fields
+}
@@ -0,0 +1,39 @@
+// A directory of its own under the temp dir, for a command that compiles an example or an exercise
+// on its own. Two of them at once, e.g. parallel tests, must not write and run the same files, so
+// the name takes the process id and a count. The directory goes away when the Scratch is dropped.
+
+use std::{
+    env, fs, io,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+pub struct Scratch(PathBuf);
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+impl Scratch {
+    // e.g. `Scratch::new("dm-mir-drop_order2")`
+    pub fn new(prefix: &str) -> io::Result<Scratch> {
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let dir = env::temp_dir().join(format!("{}-{}-{}", prefix, process::id(), count));
+        fs::create_dir_all(&dir)?;
+        Ok(Scratch(dir))
+    }
+}
+
+impl Deref for Scratch {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::tracker;
+use std::{marker::PhantomPinned, pin::Pin, ptr};
+
+// A struct that points into one of its own fields can't be expressed with references, so it holds
+// a raw pointer. It's right as long as the struct stays where it is. But a move in Rust is a copy
+// of the bytes to another place, and nothing updates the pointer: after a move it points to where
+// the struct used to be. A Drop that read through it would read memory that isn't the struct
+// anymore, maybe not even memory at all. That's why the Drop below only compares addresses.
+#[allow(unused)]
+pub fn self_ref1() {
+    struct SelfRef {
+        name: String,
+        this: *const String,
+    }
+    impl SelfRef {
+        fn new(name: &str) -> SelfRef {
+            SelfRef {
+                name: name.to_owned(),
+                this: ptr::null(),
+            }
+        }
+        fn init(&mut self) {
+            self.this = &self.name;
+        }
+        fn points_to_itself(&self) -> bool {
+            ptr::eq(self.this, &self.name)
+        }
+    }
+    impl Drop for SelfRef {
+        fn drop(&mut self) {
+            tracker::dropped("SelfRef");
+            println!(
+                "SelfRef dropped, still points to itself: {}",
+                self.points_to_itself()
+            );
+        }
+    }
+
+    let mut a = SelfRef::new("a");
+    a.init();
+    println!(
+        "before the move, points to itself: {}",
+        a.points_to_itself()
+    );
+    let moved = Box::new(a);
+    println!(
+        "moved to the heap, points to itself: {}",
+        moved.points_to_itself()
+    );
+    // moved gets dropped here, with a pointer to where it was on the stack
+}
+
+// Pin is a promise about a place rather than a value: once a value is pinned, it stays at that
+// address until it is dropped. PhantomPinned opts the struct out of Unpin, so that the promise
+// binds: through a Pin<Box<Pinned>> there's no &mut Pinned to mem::swap or mem::replace with. The
+// Box itself may be moved around, the value on the heap is not. And since it is dropped where it
+// was pinned, its Drop may rely on the pointer.
+#[allow(unused)]
+pub fn self_ref2() {
+    struct Pinned {
+        name: String,
+        this: *const String,
+        _pinned: PhantomPinned,
+    }
+    impl Pinned {
+        fn new(name: &str) -> Pin<Box<Pinned>> {
+            let mut boxed = Box::pin(Pinned {
+                name: name.to_owned(),
+                this: ptr::null(),
+                _pinned: PhantomPinned,
+            });
+            let this = &boxed.name as *const String;
+            // Safety: only a field is set, the value is not moved out
+            unsafe { boxed.as_mut().get_unchecked_mut().this = this };
+            boxed
+        }
+    }
+    impl Drop for Pinned {
+        fn drop(&mut self) {
+            tracker::dropped("Pinned");
+            // Safety: pinned until now, so the pointer is still right
+            let name = unsafe { &*self.this };
+            println!("Pinned dropped, read {} through its own pointer", name);
+        }
+    }
+
+    let a = Pinned::new("pinned");
+    let moved = a;
+    println!(
+        "the box moved, its value did not: {}",
+        ptr::eq(moved.this, &moved.name)
+    );
+    // uncomment the following line to see the error: Pinned is not Unpin
+    // std::mem::swap(&mut *moved, &mut *Pinned::new("other"));
+}
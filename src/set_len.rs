@@ -0,0 +1,106 @@
+use crate::util::{DropSpy, NoisyDrop};
+use std::{panic, ptr};
+
+// `Vec::set_len` changes the length and nothing else: no element is dropped or initialized. The
+// length is what the Vec drops when it is dropped, the capacity only what it frees. Shrinking it
+// is the safe direction, the elements past the new length are merely forgotten: neither the
+// NoisyDrop values nor the Strings they own are ever dropped, and the summary after the example
+// reports the Strings as still allocated. It is unsafe anyway, since the other direction would
+// expose elements that were never written.
+#[allow(unused)]
+pub fn set_len1() {
+    let mut vec = vec![
+        NoisyDrop("kept", String::from("kept")),
+        NoisyDrop("leaked", String::from("leaked")),
+        NoisyDrop("leaked too", String::from("leaked too")),
+    ];
+    // SAFETY: 1 is not more than the length, and the element at 0 stays initialized
+    unsafe { vec.set_len(1) };
+    println!("{} element left, the other two are forgotten", vec.len());
+    // vec gets dropped here, with the first element only
+}
+
+// The other way round, a length too long counts an element that is no longer the Vec's. The broken
+// `split_off` below moves the tail out into a new Vec with a bitwise copy, then mixes up the two
+// lengths: the Vec keeps `len - at` elements instead of `at`. b is now in both Vecs, and both would
+// drop it, freeing its String twice. That is undefined behavior, so a normal run stops short of it
+// and puts the length right, while `dm miri set_len2` lets both drop and has Miri catch the double
+// free. Vec::split_off does it right.
+#[allow(unused)]
+pub fn set_len2() {
+    fn broken_split_off(vec: &mut Vec<NoisyDrop<String>>, at: usize) -> Vec<NoisyDrop<String>> {
+        let count = vec.len() - at;
+        let mut tail = Vec::with_capacity(count);
+        // SAFETY: none, the length kept should be `at`, not the count moved out
+        unsafe {
+            ptr::copy_nonoverlapping(vec.as_ptr().add(at), tail.as_mut_ptr(), count);
+            tail.set_len(count);
+            vec.set_len(count);
+        }
+        tail
+    }
+
+    let mut vec = vec![
+        NoisyDrop("a", String::from("a")),
+        NoisyDrop("b", String::from("b")),
+        NoisyDrop("c", String::from("c")),
+    ];
+    let tail = broken_split_off(&mut vec, 1);
+    println!(
+        "the Vec keeps {} elements and the tail has {}, b is in both",
+        vec.len(),
+        tail.len()
+    );
+    if !cfg!(miri) {
+        println!("dropping both would drop b twice, `dm miri set_len2` shows it");
+        // SAFETY: the length it should have been, a is the only element left that is the Vec's
+        unsafe { vec.set_len(1) };
+    }
+    // tail gets dropped here, then vec
+}
+
+// The length should only ever count initialized elements, even if a panic gets in the way. Code
+// that fills the spare capacity by hand therefore bumps the length from a guard, like the
+// SetLenOnDrop of std: the guard counts the elements written so far, and its Drop stores the
+// count in the Vec on every way out, unwinding included. When `make` panics at the third
+// element, the Vec ends up with the two that exist, and drops them.
+#[allow(unused)]
+pub fn set_len3() {
+    struct SetLenOnDrop<'a, T> {
+        vec: &'a mut Vec<T>,
+        len: usize,
+    }
+    impl<T> Drop for SetLenOnDrop<'_, T> {
+        fn drop(&mut self) {
+            println!("the guard sets the length to {}", self.len);
+            // SAFETY: the first len elements have been written
+            unsafe { self.vec.set_len(self.len) };
+        }
+    }
+
+    fn fill(vec: &mut Vec<DropSpy>, names: &[&'static str], make: fn(&'static str) -> DropSpy) {
+        vec.reserve(names.len());
+        let mut guard = SetLenOnDrop {
+            len: vec.len(),
+            vec,
+        };
+        for &name in names {
+            // SAFETY: reserved above, the slot is past the length and not initialized
+            unsafe { guard.vec.as_mut_ptr().add(guard.len).write(make(name)) };
+            guard.len += 1;
+        }
+    }
+
+    let mut vec = Vec::new();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        fill(&mut vec, &["a", "b", "c"], |name| {
+            if name == "c" {
+                panic!("failed to make c");
+            }
+            DropSpy(name)
+        })
+    }));
+    println!("caught the panic: {}", result.is_err());
+    println!("the Vec holds {} elements", vec.len());
+    // vec gets dropped here, and a and b with it
+}
@@ -0,0 +1,203 @@
+use crate::tracker;
+use std::{cell::Cell, marker::PhantomData, ptr::NonNull};
+
+// A Rc of our own. The value lives on the heap in a control block next to a count of its owners.
+// Every clone is a pointer to the same block and adds one to the count, every drop takes one away,
+// and only the drop that brings it to zero drops the value and frees the block. So Drop for MyRc
+// runs once per clone, but the value inside is dropped once, by whichever clone happens to be last.
+//
+// MyRc holds a raw pointer, which owns nothing as far as the drop checker is concerned. The
+// PhantomData<RcBox<T>> says that a MyRc may drop a T. With a plain `impl Drop` that goes without
+// saying, the checker assumes the worst anyway. The std Rc also has `#[may_dangle]` on its Drop,
+// and like for MyBox in phantom3, that is only sound together with the PhantomData.
+#[allow(unused)]
+pub fn shared1() {
+    struct RcBox<T> {
+        strong: Cell<usize>,
+        value: T,
+    }
+    struct MyRc<T> {
+        ptr: NonNull<RcBox<T>>,
+        _owns: PhantomData<RcBox<T>>,
+    }
+    impl<T> MyRc<T> {
+        fn new(value: T) -> MyRc<T> {
+            let boxed = Box::new(RcBox {
+                strong: Cell::new(1),
+                value,
+            });
+            MyRc {
+                ptr: NonNull::from(Box::leak(boxed)),
+                _owns: PhantomData,
+            }
+        }
+        fn inner(&self) -> &RcBox<T> {
+            // Safety: the block lives as long as any MyRc points to it
+            unsafe { self.ptr.as_ref() }
+        }
+        fn strong_count(&self) -> usize {
+            self.inner().strong.get()
+        }
+    }
+    impl<T> Clone for MyRc<T> {
+        fn clone(&self) -> MyRc<T> {
+            let strong = &self.inner().strong;
+            strong.set(strong.get() + 1);
+            MyRc {
+                ptr: self.ptr,
+                _owns: PhantomData,
+            }
+        }
+    }
+    impl<T> Drop for MyRc<T> {
+        fn drop(&mut self) {
+            let strong = &self.inner().strong;
+            strong.set(strong.get() - 1);
+            println!("a MyRc dropped, {} left", strong.get());
+            if strong.get() == 0 {
+                // Safety: this was the last owner, nobody can see the block anymore
+                unsafe { drop(Box::from_raw(self.ptr.as_ptr())) };
+            }
+        }
+    }
+    struct Value;
+    impl Drop for Value {
+        fn drop(&mut self) {
+            tracker::dropped("Value");
+            println!("Value dropped along with the last MyRc");
+        }
+    }
+
+    let first = MyRc::new(Value);
+    let second = first.clone();
+    {
+        let third = second.clone();
+        println!("{} owners", third.strong_count());
+        // third gets dropped here
+    }
+    drop(first);
+    println!("second is the last owner: {}", second.strong_count() == 1);
+    // second gets dropped here, and the value with it
+}
+
+// The same with an atomic count is an Arc, whose clones can be sent to other threads. Then the
+// last owner, the one to drop the value, may be on any thread: here it is the worker, since main
+// lets go of its clone first.
+//
+// Decrementing with Release makes everything a thread did with the value happen before the count
+// goes down. The thread that takes it to zero then needs an Acquire fence to see all of that
+// before it drops the value, or the Drop might run while the writes of another thread are still
+// on their way. A count that only goes up in `clone` needs no ordering at all.
+#[allow(unused)]
+pub fn shared2() {
+    use std::{
+        sync::{
+            atomic::{fence, AtomicUsize, Ordering},
+            mpsc,
+        },
+        thread,
+    };
+
+    struct ArcInner<T> {
+        strong: AtomicUsize,
+        value: T,
+    }
+    struct MyArc<T> {
+        ptr: NonNull<ArcInner<T>>,
+        _owns: PhantomData<ArcInner<T>>,
+    }
+    // Shared between threads, so both Send and Sync are needed from T
+    unsafe impl<T: Send + Sync> Send for MyArc<T> {}
+    unsafe impl<T: Send + Sync> Sync for MyArc<T> {}
+    impl<T> MyArc<T> {
+        fn new(value: T) -> MyArc<T> {
+            let boxed = Box::new(ArcInner {
+                strong: AtomicUsize::new(1),
+                value,
+            });
+            MyArc {
+                ptr: NonNull::from(Box::leak(boxed)),
+                _owns: PhantomData,
+            }
+        }
+        fn inner(&self) -> &ArcInner<T> {
+            // Safety: the block lives as long as any MyArc points to it
+            unsafe { self.ptr.as_ref() }
+        }
+    }
+    impl<T> Clone for MyArc<T> {
+        fn clone(&self) -> MyArc<T> {
+            self.inner().strong.fetch_add(1, Ordering::Relaxed);
+            MyArc {
+                ptr: self.ptr,
+                _owns: PhantomData,
+            }
+        }
+    }
+    impl<T> Drop for MyArc<T> {
+        fn drop(&mut self) {
+            if self.inner().strong.fetch_sub(1, Ordering::Release) != 1 {
+                return;
+            }
+            fence(Ordering::Acquire);
+            // Safety: this was the last owner, on whatever thread it is
+            unsafe { drop(Box::from_raw(self.ptr.as_ptr())) };
+        }
+    }
+    struct Value;
+    impl Drop for Value {
+        fn drop(&mut self) {
+            tracker::dropped("Value");
+            let thread = thread::current();
+            println!(
+                "Value dropped on the {} thread",
+                thread.name().unwrap_or("unnamed")
+            );
+        }
+    }
+
+    let arc = MyArc::new(Value);
+    let clone = arc.clone();
+    let (go, wait) = mpsc::channel::<()>();
+    let worker = thread::Builder::new()
+        .name("worker".to_owned())
+        .spawn(move || {
+            let _ = wait.recv();
+            drop(clone);
+        })
+        .unwrap();
+    drop(arc);
+    println!("main dropped its MyArc, the worker still has one");
+    drop(go);
+    worker.join().unwrap();
+}
+
+// `Rc::into_raw` turns a Rc into a pointer to its value without touching the count: the count the
+// Rc held now belongs to the pointer, and the value stays alive however long the pointer is passed
+// around. `Rc::from_raw` turns that count back into a Rc, and `increment_strong_count` and
+// `decrement_strong_count` add or take away counts without a Rc at all. The value is dropped when
+// the count gets to zero, whichever of them does it. Each count must be given back exactly once:
+// one from_raw too few leaks the value, one too many drops it while it is still in use.
+#[allow(unused)]
+pub fn shared3() {
+    use std::rc::Rc;
+
+    struct Value;
+    impl Drop for Value {
+        fn drop(&mut self) {
+            tracker::dropped("Value");
+            println!("Value dropped with the last count");
+        }
+    }
+
+    let raw = Rc::into_raw(Rc::new(Value));
+    // SAFETY: raw comes from Rc::into_raw and its Rc is still alive
+    unsafe { Rc::increment_strong_count(raw) };
+    let rc = unsafe { Rc::from_raw(raw) };
+    println!("{} counts, one of them in rc", Rc::strong_count(&rc));
+    drop(rc);
+    println!("rc is gone, the pointer still holds a count");
+    // SAFETY: the pointer gives back the count it holds, and is not used afterwards
+    unsafe { Rc::decrement_strong_count(raw) };
+    println!("both counts are given back");
+}
@@ -0,0 +1,36 @@
+// The reference solution of an exercise, for when `check` keeps saying no. Every exercise has one
+// of the same name in exercises/solutions/, which `check` passes. With `--diff`, only what the
+// solution changes in the attempt is shown, as a unified diff, since the rest of the file is the
+// same and the difference is the whole point.
+
+use crate::check;
+use similar::TextDiff;
+use std::{fs, io, path::PathBuf};
+
+pub fn path(name: &str) -> PathBuf {
+    check::dir().join("solutions").join(format!("{}.rs", name))
+}
+
+pub fn run(name: &str, diff: bool) -> io::Result<()> {
+    let solution = fs::read_to_string(path(name))?;
+    if !diff {
+        print!("{}", solution);
+        return Ok(());
+    }
+    let attempt_path = check::dir().join(format!("{}.rs", name));
+    let attempt = fs::read_to_string(&attempt_path)?;
+    if attempt == solution {
+        println!("{} is the same as the solution", attempt_path.display());
+        return Ok(());
+    }
+    let diff = TextDiff::from_lines(&attempt, &solution);
+    let (old, new) = (
+        attempt_path.display().to_string(),
+        path(name).display().to_string(),
+    );
+    print!(
+        "{}",
+        diff.unified_diff().context_radius(3).header(&old, &new)
+    );
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::tracker;
+use std::{
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+struct Config(&'static str);
+
+impl Drop for Config {
+    fn drop(&mut self) {
+        tracker::dropped(self.0);
+        println!("{} dropped", self.0);
+    }
+}
+
+// A static is never dropped, not when main returns, not ever. It can be borrowed for 'static by
+// anyone, other threads and other destructors included, so there is no moment at which dropping
+// it would be safe: something could still be reading it. Rust doesn't try, and since leaking is
+// safe (see forget1) it doesn't have to. A const is the opposite: it is no place in memory but a
+// value pasted wherever it is used, so every use is a new Config, dropped like any other.
+//
+// A singleton created at runtime ends the same way. `instance` makes its Config on the first call
+// and leaks it on purpose, so that it can hand out a &'static to it. The summary after the example
+// reports the leaked Box, and nothing ever prints that the singleton was dropped.
+#[allow(unused)]
+pub fn statics1() {
+    static DEFAULT: Config = Config("static");
+    const FRESH: Config = Config("const");
+
+    fn instance() -> &'static Config {
+        static INSTANCE: AtomicPtr<Config> = AtomicPtr::new(ptr::null_mut());
+        let current = INSTANCE.load(Ordering::Acquire);
+        if !current.is_null() {
+            // Safety: only ever set to a leaked Box, which is never freed
+            return unsafe { &*current };
+        }
+        let new = Box::into_raw(Box::new(Config("singleton")));
+        match INSTANCE.compare_exchange(current, new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => unsafe { &*new },
+            // Another thread was faster, our Config was never shared and can go
+            Err(existing) => unsafe {
+                drop(Box::from_raw(new));
+                &*existing
+            },
+        }
+    }
+
+    println!("{} lives for the whole program", DEFAULT.0);
+    let fresh = FRESH;
+    println!("{} is a new value at every use", fresh.0);
+    let first = instance();
+    let second = instance();
+    println!("the same singleton twice: {}", ptr::eq(first, second));
+    // fresh gets dropped here, DEFAULT and the singleton never are
+}
+
+// A OnceLock is set at most once, and drops its value like any other field when it is dropped
+// itself. So a local OnceLock drops the value with it at the end of the block, and a OnceLock
+// that was never set has nothing to drop. In a static, the value is set once and then lives
+// forever like DEFAULT in statics1, which makes OnceLock the safe way to write `instance`.
+#[allow(unused)]
+pub fn statics2() {
+    use std::sync::OnceLock;
+
+    static GLOBAL: OnceLock<Config> = OnceLock::new();
+
+    let local = OnceLock::new();
+    let unset = OnceLock::<Config>::new();
+    local.get_or_init(|| Config("local"));
+    local.get_or_init(|| unreachable!("only the first call initializes"));
+    GLOBAL.get_or_init(|| Config("global"));
+    println!(
+        "{} and {} are set",
+        local.get().unwrap().0,
+        GLOBAL.get().unwrap().0
+    );
+    // unset gets dropped here, with nothing in it
+    // local gets dropped here, and its Config with it
+}
+
+// A LazyLock runs its closure on the first access. A lazy static that is never used is never
+// constructed, so there is nothing to drop, and that goes for a local one as well. If the closure
+// panics, the LazyLock is poisoned: every later access panics too, and the value never exists.
+// Either way the Drop of the value doesn't run, but there was no value to begin with.
+#[allow(unused)]
+pub fn statics3() {
+    use std::{panic, sync::LazyLock};
+
+    static UNUSED: LazyLock<Config> = LazyLock::new(|| Config("unused"));
+    static FAILING: LazyLock<Config> = LazyLock::new(|| panic!("the initialization failed"));
+
+    let used = LazyLock::new(|| {
+        println!("constructing the used Config");
+        Config("used")
+    });
+    let idle = LazyLock::new(|| Config("idle"));
+    println!("before the first access");
+    println!("{} is constructed now", used.0);
+    let first = panic::catch_unwind(|| FAILING.0).is_err();
+    let second = panic::catch_unwind(|| FAILING.0).is_err();
+    println!("both accesses to FAILING panicked: {}", first && second);
+    // idle gets dropped here, without having been constructed
+    // used gets dropped here, and its Config with it
+}
@@ -0,0 +1,185 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    panic,
+    sync::{Mutex, PoisonError},
+};
+
+// The standard library is full of guards like the ScopeGuard of guard.rs. `RefCell::borrow_mut`
+// doesn't hand out a &mut but a RefMut, which counts as a borrow of the cell until it is dropped,
+// and whose Drop gives the borrow back. So a borrow of a RefCell lasts as long as its guard, and
+// the drop order lessons tell exactly how long that is.
+#[allow(unused)]
+pub fn std_guards1() {
+    let cell = RefCell::new(vec![1, 2]);
+    let mut guard = cell.borrow_mut();
+    guard.push(3);
+    println!(
+        "borrowed while the RefMut lives: {}",
+        cell.try_borrow().is_err()
+    );
+    drop(guard);
+    println!(
+        "borrowed once it is dropped: {}",
+        cell.try_borrow().is_err()
+    );
+    {
+        let first = cell.borrow();
+        let second = cell.borrow();
+        println!("two Refs at once: {} and {}", first.len(), second.len());
+        // second and first get dropped here, and give their shared borrows back
+    }
+    println!(
+        "borrowed after the block: {}",
+        cell.try_borrow_mut().is_err()
+    );
+}
+
+// A MutexGuard holds the lock until it is dropped. The pitfall is to drop it too early without
+// noticing: `let _ = ...` binds nothing, so the guard is a temporary dropped at the semicolon, and
+// what should have been done under the lock is not. `let _guard = ...` binds it to a variable,
+// which lives until the end of the block. The mistake is common enough that rustc denies
+// `let _ = ` on a lock, hence the allow, but it lets the same thing with a RefMut go unnoticed.
+#[allow(unused, let_underscore_lock)]
+pub fn std_guards2() {
+    let mutex = Mutex::new(0);
+    let _ = mutex.lock().unwrap();
+    println!("locked after let _: {}", mutex.try_lock().is_err());
+    let _guard = mutex.lock().unwrap();
+    println!("locked after let _guard: {}", mutex.try_lock().is_err());
+    // _guard gets dropped here, and unlocks the mutex
+}
+
+// The other pitfall is to hold a guard too long. A guard bound with `let` is only dropped at the
+// end of the block, so everything after it runs under the lock, here a call that needs the lock
+// too. With `lock` instead of `try_lock` that call would deadlock. Drop the guard as soon as you
+// are done with it, or give it a block of its own. See temporaries4 for the same pitfall with the
+// temporary guard of a match scrutinee.
+#[allow(unused)]
+pub fn std_guards3() {
+    fn report(mutex: &Mutex<Vec<i32>>) {
+        match mutex.try_lock() {
+            Ok(items) => println!("report: {} items", items.len()),
+            Err(_) => println!("report: the mutex is still locked"),
+        }
+    }
+
+    let mutex = Mutex::new(vec![1]);
+    let mut items = mutex.lock().unwrap();
+    items.push(2);
+    report(&mutex);
+    drop(items);
+
+    {
+        let mut items = mutex.lock().unwrap();
+        items.push(3);
+        // items gets dropped here
+    }
+    report(&mutex);
+}
+
+// The Drop of a MutexGuard checks whether the thread is panicking, like the OnUnwind guard of
+// guard2. If it is, the data may have been left half updated, so the guard poisons the mutex on
+// its way out, and every later `lock` returns an error. The data is still there for whoever knows
+// how to check it, through PoisonError::into_inner. std_guards6 builds a poisoning lock of its own.
+#[allow(unused)]
+pub fn std_guards4() {
+    let mutex = Mutex::new(vec![1, 2]);
+    let result = panic::catch_unwind(|| {
+        let mut items = mutex.lock().unwrap();
+        items.push(3);
+        panic!("the update failed halfway");
+    });
+    println!("caught the panic: {}", result.is_err());
+    println!("poisoned: {}", mutex.is_poisoned());
+    let items = mutex.lock().unwrap_or_else(PoisonError::into_inner);
+    println!("the data is still there: {:?}", *items);
+}
+
+// A HashMap Entry borrows the map mutably too, but it has no Drop. With nothing to run at the end
+// of the scope, the borrow checker lets the borrow end at its last use, so the map can be used
+// again right after `or_insert`. A RefMut in the same spot is still alive until the end of the
+// block, since its Drop has yet to run, and the cell stays borrowed.
+#[allow(unused)]
+pub fn std_guards5() {
+    let mut map = HashMap::new();
+    let count = map.entry("a").or_insert(0);
+    *count += 1;
+    println!("the map can be read again: {} entry", map.len());
+
+    let cell = RefCell::new(0);
+    let mut count = cell.borrow_mut();
+    *count += 1;
+    println!("the cell can be read again: {}", cell.try_borrow().is_ok());
+    // count gets dropped here, only now the cell can be read again
+}
+
+// Poisoning is nothing but a Drop that looks at `thread::panicking()`. The lock below does it the
+// way Mutex does, on a single thread: its guard sets the flag if it is dropped while unwinding, and
+// `lock` checks the flag before handing out a guard, the guard going into the PoisonError either
+// way. With a Mutex the panic can be on another thread, as in the second half: the guard of the
+// thread that panicked still gets dropped while the thread unwinds, so the mutex it held is
+// poisoned for everybody else, until somebody calls `clear_poison`.
+#[allow(unused)]
+pub fn std_guards6() {
+    use std::{
+        cell::{Cell, RefMut},
+        sync::{Arc, LockResult},
+        thread,
+    };
+
+    struct Lock<T> {
+        data: RefCell<T>,
+        poisoned: Cell<bool>,
+    }
+    struct Guard<'a, T> {
+        data: RefMut<'a, T>,
+        poisoned: &'a Cell<bool>,
+    }
+    impl<T> Lock<T> {
+        fn new(value: T) -> Lock<T> {
+            Lock {
+                data: RefCell::new(value),
+                poisoned: Cell::new(false),
+            }
+        }
+        fn lock(&self) -> LockResult<Guard<'_, T>> {
+            let guard = Guard {
+                data: self.data.borrow_mut(),
+                poisoned: &self.poisoned,
+            };
+            if self.poisoned.get() {
+                Err(PoisonError::new(guard))
+            } else {
+                Ok(guard)
+            }
+        }
+    }
+    impl<T> Drop for Guard<'_, T> {
+        fn drop(&mut self) {
+            if thread::panicking() {
+                println!("Guard dropped while unwinding, the lock is poisoned");
+                self.poisoned.set(true);
+            }
+        }
+    }
+
+    let lock = Lock::new(0);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let mut guard = lock.lock().unwrap();
+        *guard.data += 1;
+        panic!("the update failed halfway");
+    }));
+    println!("our lock is poisoned: {}", lock.lock().is_err());
+
+    let mutex = Arc::new(Mutex::new(0));
+    let shared = Arc::clone(&mutex);
+    let worker = thread::spawn(move || {
+        let _guard = shared.lock().unwrap();
+        panic!("the worker failed while holding the lock");
+    });
+    println!("the worker panicked: {}", worker.join().is_err());
+    println!("the Mutex is poisoned: {}", mutex.lock().is_err());
+    mutex.clear_poison();
+    println!("poisoned after clear_poison: {}", mutex.lock().is_err());
+}
@@ -0,0 +1,117 @@
+use crate::util::DropSpy;
+use std::cell::RefCell;
+
+// A temporary is a value that is not bound to a variable, like the DropSpy passed by reference
+// below. It is dropped at the end of the statement that created it, that is at the semicolon, and
+// not at the end of the block like a variable. So x only keeps what was copied out of the
+// temporary, and a temporary inside `println!` lives long enough to be printed.
+#[allow(unused)]
+pub fn temporaries1() {
+    fn name(spy: &DropSpy) -> &'static str {
+        spy.0
+    }
+
+    let x = name(&DropSpy("temporary"));
+    println!("x is {}, and the temporary is already gone", x);
+    let a = DropSpy("a");
+    println!(
+        "{} is still there while this line is printed",
+        name(&DropSpy("another temporary"))
+    );
+    println!("a lives until the end of the block");
+}
+
+// The classic pitfall is to keep a reference into a temporary: `let x = first(&make_temp());` is
+// fine until x is used after the statement, when the temporary is already gone. The borrow checker
+// catches it, the fix is to give the temporary a name so that it lives to the end of the block.
+#[allow(unused)]
+pub fn temporaries2() {
+    fn first(spies: &[DropSpy; 2]) -> &DropSpy {
+        &spies[0]
+    }
+
+    // uncomment the following two lines to see the error (or read tests/ui/temporaries2.stderr)
+    // let x = first(&[DropSpy("x"), DropSpy("y")]);
+    // println!("{} borrowed from a temporary", x.0);
+    let spies = [DropSpy("x"), DropSpy("y")];
+    let x = first(&spies);
+    println!("{} borrowed from spies, which lives long enough", x.0);
+}
+
+// There is an exception to the end of the statement. When `let` binds a reference to a temporary,
+// the temporary is extended to live as long as the variable, to the end of the block. It applies
+// when the `&` is the whole initializer, or sits directly in a tuple, struct or array literal
+// there. Nested in any other expression, e.g. the argument of a function call, it is not
+// extended and dropped at the semicolon as usual, even if it looks just the same.
+#[allow(unused)]
+pub fn temporaries3() {
+    let extended = &DropSpy("extended");
+    let tuple = (&DropSpy("in a tuple"), 0);
+    let len = std::convert::identity(&DropSpy("nested in a call")).0.len();
+    println!("the end of the block");
+    // tuple gets dropped here, and the temporary it borrows with it
+    // extended gets dropped here, and the temporary it borrows with it
+}
+
+// The statement that owns the temporaries of a `match` scrutinee is the whole match, so they live
+// until the end of it, arms included. Usually nobody notices. But when the temporary is a guard,
+// like the Ref returned by `RefCell::borrow` or the one of `Mutex::lock`, the guard is held in all
+// the arms: `borrow_mut` there panics, and `lock` deadlocks. Take what you need out of the guard
+// in a `let` first, so that the guard is gone before the match.
+#[allow(unused, clippy::match_single_binding, clippy::single_match)]
+pub fn temporaries4() {
+    let name = DropSpy("in a let").0;
+    match DropSpy("in a match").0 {
+        name => println!("inside the match, {} is still there", name),
+    }
+
+    let cell = RefCell::new(vec![1]);
+    match cell.borrow().first() {
+        Some(&first) => println!("cell is still borrowed: {}", cell.try_borrow_mut().is_err()),
+        None => {}
+    }
+    let first = cell.borrow().first().copied();
+    match first {
+        Some(first) => cell.borrow_mut().push(first),
+        None => {}
+    }
+    println!(
+        "cell is {:?} after the match without a guard",
+        cell.borrow()
+    );
+}
+
+// How long the temporaries of an `if let` scrutinee live changed with edition 2024. Up to edition
+// 2021, `if let` is like `match`: they live until the end of the whole if-else, so a guard there
+// is still held in the `else` branch, where it is of no use. Since 2024, they are dropped before
+// the `else` runs. This crate is edition 2021, so the second half is the very same code compiled
+// as edition 2024 in the small dm-2024 crate, see dm-2024/src/lib.rs.
+#[allow(unused)]
+pub fn temporaries5() {
+    if let "never" = DropSpy("scrutinee in 2021").0 {
+    } else {
+        println!("in the else branch in 2021");
+    }
+    dm_2024::if_let();
+}
+
+// `let PATTERN = EXPR else { ... };` either binds the pattern or runs the else block, which must
+// diverge: return, break, continue or panic. Either way the temporaries of EXPR are dropped first,
+// at the end of the `let` on success and before the else block otherwise, much like `if let` in
+// edition 2024. What the pattern binds is a variable like any other and lives to the end of the
+// block.
+#[allow(unused)]
+pub fn temporaries6() {
+    fn parse(spy: &DropSpy, input: &str) -> Option<DropSpy> {
+        input.parse::<i32>().ok().map(|_| DropSpy("number"))
+    }
+
+    for input in ["42", "forty-two"] {
+        let Some(number) = parse(&DropSpy("temporary"), input) else {
+            println!("{} is not a number", input);
+            continue;
+        };
+        println!("{} is a number", input);
+        // number gets dropped here
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{tracker, util::DropSpy};
+use std::{sync::mpsc, thread};
+
+// Dropping a JoinHandle doesn't wait for its thread, it detaches it: the thread goes on without
+// anybody to join it, and what was moved into it is dropped whenever the thread gets to it. Here
+// the worker waits for a signal that comes after the handle is gone, and only then drops its
+// DropSpy. The second channel is all that's left to know when that happened.
+#[allow(unused)]
+pub fn threads1() {
+    let (go, wait) = mpsc::channel::<()>();
+    let (finished, done) = mpsc::channel::<()>();
+    let spy = DropSpy("moved into the worker");
+    let handle = thread::spawn(move || {
+        let spy = spy;
+        let _ = wait.recv();
+        println!("the worker is done with {}", spy.0);
+        drop(spy);
+        let _ = finished.send(());
+    });
+    drop(handle);
+    println!("the JoinHandle is dropped, the worker still runs");
+    drop(go);
+    let _ = done.recv();
+}
+
+// A detached thread that never finishes never drops what it owns. When main returns, the process
+// exits with the thread still parked, and the DropSpy moved into it dies with the process without
+// a Drop. This is one more way to skip a destructor in safe code, next to mem::forget and
+// Box::leak, so unsafe code can't rely on a Drop to run. The summary after the example reports
+// what the thread keeps allocated.
+#[allow(unused)]
+pub fn threads2() {
+    let spy = DropSpy("moved into a parked thread");
+    thread::spawn(move || {
+        let spy = spy;
+        loop {
+            thread::park();
+        }
+    });
+    println!("nobody waits for the parked thread, its DropSpy is never dropped");
+}
+
+// The values of a `thread_local!` belong to their thread, and are dropped when it exits, after
+// the closure returns but before `join` does. Here in the reverse order of their first use, though
+// that is not guaranteed. While they are being dropped, a key that is already gone can't be read:
+// `try_with` returns an AccessError, and `with` would panic instead. That panic would be in a
+// destructor run by the thread's exit, which aborts the process.
+//
+// The main thread is another story: its thread-locals are dropped when the process exits, if at
+// all, depending on the platform. A Drop that matters shouldn't live in a thread-local of main.
+#[allow(unused)]
+pub fn threads3() {
+    struct Local(&'static str);
+    impl Drop for Local {
+        fn drop(&mut self) {
+            tracker::dropped(self.0);
+            let first = FIRST.try_with(|_| "still there").unwrap_or("already gone");
+            println!("{} dropped on thread exit, FIRST is {}", self.0, first);
+            // uncomment the following line to see the panic
+            // FIRST.with(|_| {});
+        }
+    }
+    thread_local! {
+        static FIRST: Local = const { Local("FIRST") };
+        static SECOND: Local = const { Local("SECOND") };
+    }
+
+    thread::spawn(|| {
+        FIRST.with(|_| {});
+        SECOND.with(|_| {});
+        println!("the closure returns");
+    })
+    .join()
+    .unwrap();
+    println!("join returned after the thread-locals were dropped");
+}
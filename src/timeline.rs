@@ -0,0 +1,92 @@
+// How many bytes an example holds on the heap while it runs, drawn in the terminal. A lesson that
+// cleans up after itself ends back at the bottom of the chart, one that leaks ends up in the air.
+
+use crate::run as run_example;
+use dm::{
+    heap::{self, Sample},
+    Example,
+};
+use std::{fmt::Write, time::Duration};
+
+const WIDTH: usize = 60;
+const HEIGHT: usize = 8;
+
+// The live bytes at the end of every column, relative to the first sample. A column without any
+// sample keeps the level of the previous one.
+fn columns(samples: &[Sample]) -> Vec<isize> {
+    let Some(first) = samples.first() else {
+        return vec![0; WIDTH];
+    };
+    let total = samples.last().map_or(Duration::ZERO, |last| last.time);
+    let mut columns = Vec::with_capacity(WIDTH);
+    let mut level = 0;
+    let mut samples = samples.iter().peekable();
+    for column in 0..WIDTH {
+        // Where the column ends, in the same unit as the timestamps
+        let end = total.as_nanos() * (column as u128 + 1) / WIDTH as u128;
+        let mut peak = None;
+        while let Some(sample) = samples.next_if(|sample| sample.time.as_nanos() <= end) {
+            level = sample.usage.since(first.usage).bytes.max(0);
+            peak = Some(peak.map_or(level, |peak: isize| peak.max(level)));
+        }
+        columns.push(peak.unwrap_or(level));
+    }
+    columns
+}
+
+pub fn render(name: &str, samples: &[Sample]) -> String {
+    let columns = columns(samples);
+    let peak = columns.iter().copied().max().unwrap_or(0);
+    let end = match (samples.first(), samples.last()) {
+        (Some(first), Some(last)) => last.usage.since(first.usage),
+        _ => Default::default(),
+    };
+    let total = samples.last().map_or(Duration::ZERO, |last| last.time);
+
+    let mut chart = format!("live bytes on the heap while {} runs\n", name);
+    let label = peak.to_string().len();
+    for row in (1..=HEIGHT).rev() {
+        let axis = if row == HEIGHT {
+            peak.to_string()
+        } else {
+            String::new()
+        };
+        let _ = write!(chart, "{:>label$} |", axis);
+        for &level in &columns {
+            // A cell is filled as soon as the level reaches into it
+            let filled = level as u128 * HEIGHT as u128 > (row as u128 - 1) * peak as u128;
+            chart.push(if filled && level > 0 { '#' } else { ' ' });
+        }
+        chart.truncate(chart.trim_end().len());
+        chart.push('\n');
+    }
+    let _ = writeln!(chart, "{:>label$} +{}", 0, "-".repeat(WIDTH));
+    let _ = writeln!(
+        chart,
+        "{:>label$}  0{:>width$?}",
+        "",
+        total,
+        width = WIDTH - 1
+    );
+    let verdict = if end.bytes > 0 {
+        "it leaked"
+    } else {
+        "everything was freed"
+    };
+    let _ = writeln!(
+        chart,
+        "{} bytes at most, {} bytes in {} blocks still allocated at the end: {}",
+        peak, end.bytes, end.blocks, verdict
+    );
+    chart
+}
+
+pub fn run(example: &Example) {
+    // Like `run` does, keep the buffer of stdout out of the chart
+    let _ = std::io::stdout();
+    heap::record();
+    run_example(example);
+    let samples = heap::stop();
+    println!();
+    print!("{}", render(example.name, &samples));
+}
@@ -0,0 +1,165 @@
+// A global log of what gets constructed and dropped, so that the drop order is not only printed
+// but can also be checked by a program. The types in the examples push an event here next to the
+// line they print, and the tests (or any other tool) take the log afterwards.
+//
+// The log is shared by all threads. Whoever reads it should make sure nobody else is running an
+// example at the same time, e.g. by taking a lock in the tests.
+
+use std::{
+    fmt::Write,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Constructed,
+    Dropped,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Constructed => "constructed",
+            Kind::Dropped => "dropped",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub kind: Kind,
+    // The name of the type, or of the variable if it tells more
+    pub name: &'static str,
+    // Since the tracker was first used by this process, usually when the example started
+    pub time: Duration,
+    // How many scopes were entered when it happened, see `enter`
+    pub depth: usize,
+}
+
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+static START: OnceLock<Instant> = OnceLock::new();
+static DEPTH: AtomicUsize = AtomicUsize::new(0);
+static WATCH: OnceLock<fn(&Event)> = OnceLock::new();
+
+// Since the tracker was first used. wasm32-unknown-unknown has no clock, so everything happens at
+// once there.
+fn elapsed() -> Duration {
+    if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+        return Duration::ZERO;
+    }
+    START.get_or_init(Instant::now).elapsed()
+}
+
+// `place` is the address and size of the value, where it is known
+fn record(kind: Kind, name: &'static str, place: Option<(*const u8, usize)>) {
+    let time = elapsed();
+    let depth = DEPTH.load(Ordering::Relaxed);
+    // A panicking example must not take the log down with it
+    let mut events = EVENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    // The log grows as the example runs, but it is not what the example left on the heap
+    let event = Event {
+        kind,
+        name,
+        time,
+        depth,
+    };
+    crate::heap::untracked(|| events.push(event.clone()));
+    drop(events);
+    match place {
+        Some((address, size)) => crate::log::log!(
+            Verbose,
+            "{} {}, {} bytes at {:p}",
+            kind.as_str(),
+            name,
+            size,
+            address
+        ),
+        None => crate::log::log!(Verbose, "{} {}", kind.as_str(), name),
+    }
+    if let Some(watch) = WATCH.get() {
+        crate::heap::untracked(|| watch(&event));
+    }
+}
+
+// Call `hook` with every event as it is recorded, on the thread that records it, e.g. to find out
+// where it happened while that is still on the stack. Only the first hook set counts.
+pub fn watch(hook: fn(&Event)) {
+    let _ = WATCH.set(hook);
+}
+
+pub fn constructed(name: &'static str) {
+    record(Kind::Constructed, name, None);
+}
+
+pub fn dropped(name: &'static str) {
+    record(Kind::Dropped, name, None);
+}
+
+// Like `dropped`, from the Drop of `value`, whose size and address then show up with `-v`
+pub fn dropped_value<T: ?Sized>(name: &'static str, value: &T) {
+    let place = (value as *const T as *const u8, std::mem::size_of_val(value));
+    record(Kind::Dropped, name, Some(place));
+}
+
+// Everything recorded since the last call, oldest first
+pub fn take() -> Vec<Event> {
+    let mut events = EVENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    std::mem::take(&mut *events)
+}
+
+// Marks a scope, e.g. the run of an example, for the `depth` of the events recorded inside it.
+// The scope ends when the guard is dropped, like any other scope in Rust.
+pub struct Scope(());
+
+pub fn enter() -> Scope {
+    elapsed();
+    DEPTH.fetch_add(1, Ordering::Relaxed);
+    Scope(())
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        DEPTH.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// The events as a JSON array, one object per line, for tools that want to consume the behavior of
+// an example rather than read it
+pub fn to_json(events: &[Event]) -> String {
+    let mut json = String::from("[");
+    for (i, event) in events.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        let mut name = String::new();
+        for c in event.name.chars() {
+            match c {
+                '"' | '\\' => {
+                    name.push('\\');
+                    name.push(c);
+                }
+                c if c.is_control() => {
+                    let _ = write!(name, "\\u{:04x}", c as u32);
+                }
+                c => name.push(c),
+            }
+        }
+        let _ = write!(
+            json,
+            "{}\n  {{\"name\": \"{}\", \"kind\": \"{}\", \"timestamp_ns\": {}, \"depth\": {}}}",
+            separator,
+            name,
+            event.kind.as_str(),
+            event.time.as_nanos(),
+            event.depth
+        );
+    }
+    json.push_str("\n]\n");
+    json
+}
@@ -0,0 +1,104 @@
+// A small terminal menu to take the tour without touching the code. The list of examples is on the
+// left, the annotation and the code of the selected one on the right, and the output of the last
+// run at the bottom.
+
+use crate::{progress::Progress, run_captured};
+use dm::{Example, EXAMPLES};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Text,
+    widgets::{Block, List, ListState, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+use std::io;
+
+struct Menu {
+    selected: ListState,
+    scroll: u16,
+    output: String,
+    progress: Progress,
+}
+
+impl Menu {
+    fn example(&self) -> &'static Example {
+        &EXAMPLES[self.selected.selected().unwrap_or(0)]
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected.select(Some(index.min(EXAMPLES.len() - 1)));
+        self.scroll = 0;
+        self.output.clear();
+    }
+
+    fn run_selected(&mut self) -> io::Result<()> {
+        self.output = run_captured(self.example().name)?;
+        // The run was recorded by the process that did it
+        self.progress = Progress::load();
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [top, bottom] =
+            Layout::vertical([Constraint::Percentage(70), Constraint::Percentage(30)])
+                .areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Length(19), Constraint::Min(0)]).areas(top);
+
+        let names = EXAMPLES
+            .iter()
+            .map(|example| format!("{} {}", self.progress.marks(example.name), example.name));
+        let names = List::new(names)
+            .block(Block::bordered().title("Examples"))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(names, left, &mut self.selected);
+
+        let (prose, code) = self.example().lesson();
+        let lesson = Paragraph::new(Text::raw(format!("{}\n\n{}", prose, code)))
+            .block(Block::bordered().title(self.example().name))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(lesson, right);
+
+        let output = Paragraph::new(self.output.as_str())
+            .block(Block::bordered().title("Output (Enter to run, PgUp/PgDn to scroll, q to quit)"))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(output, bottom);
+    }
+}
+
+pub fn run() -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    let mut menu = Menu {
+        selected: ListState::default().with_selected(Some(0)),
+        scroll: 0,
+        output: String::new(),
+        progress: Progress::load(),
+    };
+    loop {
+        terminal.draw(|frame| menu.draw(frame))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let index = menu.selected.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Up | KeyCode::Char('k') => menu.select(index.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => menu.select(index + 1),
+            KeyCode::PageUp => menu.scroll = menu.scroll.saturating_sub(10),
+            KeyCode::PageDown => menu.scroll = menu.scroll.saturating_add(10),
+            KeyCode::Enter => menu.run_selected()?,
+            _ => {}
+        }
+    }
+}
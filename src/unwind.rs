@@ -0,0 +1,98 @@
+use crate::tracker;
+use std::panic;
+
+// A panic unwinds the stack: it leaves every function on its way up as if it returned, and drops
+// the locals of each of them in the usual reverse order. Only what was initialized when the panic
+// happened is dropped, here b and a but not c, which never existed. That's what makes RAII hold
+// up under panics: the lock guards and files of the unwound functions are released as well.
+//
+// `catch_unwind` stops the unwinding here so that the example can go on, see unwind3. The message
+// of the panic is printed to stderr as usual.
+#[allow(unused)]
+pub fn unwind1() {
+    struct A(&'static str);
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped(self.0);
+            println!("{} dropped", self.0);
+        }
+    }
+    fn work(fail: bool) {
+        let a = A("a");
+        let b = A("b");
+        if fail {
+            panic!("work failed halfway");
+        }
+        let c = A("c");
+        println!("work done");
+    }
+
+    println!("normal exit:");
+    work(false);
+    println!("unwinding:");
+    let _ = panic::catch_unwind(|| work(true));
+}
+
+// A Drop that panics while a panic is already unwinding leaves Rust with two panics and nowhere to
+// go, so it aborts the whole process on the spot. Nothing else is dropped, not even a, and no
+// catch_unwind can stop it. Never panic in Drop, or at least check `std::thread::panicking()`
+// first. The process that runs this example is gone after it, so the runner starts a new one just
+// for it and reports how it ended.
+#[allow(unused)]
+pub fn unwind2() {
+    struct A();
+    struct Bomb();
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped("A");
+            println!("A dropped, but you will never see this line");
+        }
+    }
+    impl Drop for Bomb {
+        fn drop(&mut self) {
+            tracker::dropped("Bomb");
+            println!("Bomb dropped while unwinding, and it panics too");
+            panic!("the second panic");
+        }
+    }
+
+    let a = A();
+    let bomb = Bomb();
+    panic!("the first panic");
+}
+
+// `catch_unwind` runs a closure and stops a panic in it from unwinding any further. The panic is
+// handed back as an Err. Everything the closure owns is dropped on the way out as usual, while the
+// caller and its locals, like outer, are untouched and go on as if nothing happened.
+//
+// It only takes closures that are UnwindSafe. One that captures a `&mut` isn't: the panic may have
+// left the borrowed data halfway through an update, and the caller gets to see it afterwards.
+// `AssertUnwindSafe` is your promise that it's fine, like log below which simply lacks an entry.
+// It is not unsafe, only a nudge to think about it.
+#[allow(unused, unreachable_code)]
+pub fn unwind3() {
+    struct A(&'static str);
+    impl Drop for A {
+        fn drop(&mut self) {
+            tracker::dropped(self.0);
+            println!("{} dropped", self.0);
+        }
+    }
+
+    let outer = A("outer");
+    let result = panic::catch_unwind(|| {
+        let inner = A("inner");
+        panic!("the panic in the closure");
+    });
+    println!("caught the panic: {}", result.is_err());
+
+    let mut log = Vec::new();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        log.push("started");
+        let inner = A("inner again");
+        panic!("the panic in the closure again");
+        log.push("finished");
+    }));
+    println!("log after the panic: {:?}", log);
+    // outer gets dropped here, the panics never reached it
+}
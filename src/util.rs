@@ -0,0 +1,132 @@
+// Small types for the examples to drop, so that every lesson doesn't have to declare its own
+// structs with a Drop impl that prints. They all print `<name> dropped` and record the event in
+// the tracker, so the output looks the same across lessons.
+
+use crate::tracker;
+use std::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+// Nothing but a name to print when dropped
+pub struct DropSpy(pub &'static str);
+
+impl DropSpy {
+    // Record the construction too. `DropSpy("a")` works as well if only the drop is of interest.
+    pub fn new(name: &'static str) -> DropSpy {
+        tracker::constructed(name);
+        DropSpy(name)
+    }
+}
+
+impl Drop for DropSpy {
+    fn drop(&mut self) {
+        tracker::dropped_value(self.0, self);
+        println!("{} dropped", self.0);
+    }
+}
+
+// Like DropSpy but owning a value, which is dropped by the drop glue right after the name is
+// printed. Derefs to the value so that it can be used like the value itself.
+pub struct NoisyDrop<T>(pub &'static str, pub T);
+
+impl<T> NoisyDrop<T> {
+    pub fn new(name: &'static str, value: T) -> NoisyDrop<T> {
+        tracker::constructed(name);
+        NoisyDrop(name, value)
+    }
+}
+
+impl<T> Deref for NoisyDrop<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.1
+    }
+}
+
+impl<T> DerefMut for NoisyDrop<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.1
+    }
+}
+
+impl<T> Drop for NoisyDrop<T> {
+    fn drop(&mut self) {
+        tracker::dropped_value(self.0, self);
+        println!("{} dropped", self.0);
+    }
+}
+
+// Panics in its destructor, for the lessons about drops and unwinding
+pub struct PanicOnDrop(pub &'static str);
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        tracker::dropped_value(self.0, self);
+        println!("{} dropped", self.0);
+        panic!("{} panicked on drop", self.0);
+    }
+}
+
+// Counts the drops of the tokens it hands out, to check that a type drops what it owns exactly
+// once where printing would not tell: one too few is a leak, one too many a double drop. The count
+// is shared with the tokens, so it can still be read once they are gone.
+#[derive(Clone, Default)]
+pub struct DropCounter(Arc<AtomicUsize>);
+
+impl DropCounter {
+    pub fn new() -> DropCounter {
+        DropCounter::default()
+    }
+
+    pub fn token(&self) -> DropToken {
+        DropToken(Arc::clone(&self.0))
+    }
+
+    // How many of its tokens were dropped so far
+    pub fn dropped(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// Counts its drop in the DropCounter that handed it out, and on the thread that drops it
+pub struct DropToken(Arc<AtomicUsize>);
+
+thread_local! {
+    static TOKENS_DROPPED: Cell<usize> = const { Cell::new(0) };
+}
+
+impl Drop for DropToken {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        let _ = TOKENS_DROPPED.try_with(|dropped| dropped.set(dropped.get() + 1));
+    }
+}
+
+// The tokens of any DropCounter dropped on this thread so far, for `assert_drops!`
+pub fn tokens_dropped() -> usize {
+    TOKENS_DROPPED.with(Cell::get)
+}
+
+// Run a block and panic unless it dropped exactly `$n` tokens, of any DropCounter, on this thread,
+// e.g. `assert_drops!(2, { drop(pair) })`. Evaluates to the value of the block, which is not
+// dropped yet.
+#[macro_export]
+macro_rules! assert_drops {
+    ($n:expr, $block:block) => {{
+        let before = $crate::util::tokens_dropped();
+        let value = $block;
+        let dropped = $crate::util::tokens_dropped() - before;
+        assert_eq!(
+            dropped, $n,
+            "expected {} tokens dropped, got {}",
+            $n, dropped
+        );
+        value
+    }};
+}
@@ -0,0 +1,50 @@
+use crate::tracker;
+use std::cell::Cell;
+
+// A type is covariant in a lifetime when a longer lifetime can stand in for a shorter one, like
+// &'static str where a &'a str is expected. So when a type with a Drop impl mixes references of
+// different lifetimes, the compiler shrinks them all to the shortest one, and that's the one the
+// drop checker makes strictly outlive the value. Below, `inspectors` is an [Inspector<'x>; 2]
+// where 'x is no longer than short, so short must be declared before inspectors. long may be
+// anywhere before it: it simply outlives 'x.
+#[allow(unused, clippy::needless_late_init)]
+pub fn variance1() {
+    struct Inspector<'a>(&'a str);
+    impl Drop for Inspector<'_> {
+        fn drop(&mut self) {
+            tracker::dropped("Inspector");
+            println!("Inspector dropped, it still reads {}", self.0);
+        }
+    }
+
+    let long = String::from("long");
+    let short = String::from("short");
+    let inspectors = [Inspector(&long), Inspector(&short)];
+    // uncomment the following three lines to see the error (or read tests/ui/variance1.stderr)
+    // let late;
+    // let shorter = String::from("shorter");
+    // late = [Inspector(&long), Inspector(&shorter)];
+}
+
+// A Cell<&'a str> can be written through a shared reference, so it must be invariant in 'a: if it
+// could shrink, a short-lived &str could be written where a &'static str is expected. An
+// Inspector over a Cell keeps exactly the lifetime it was created with. One that holds a
+// &'static str can't join one that borrows a local, even if the local outlives both. The
+// covariant Inspector of variance1 gets along with the same code.
+#[allow(unused)]
+pub fn variance2() {
+    struct Inspector<'a>(Cell<&'a str>);
+    impl Drop for Inspector<'_> {
+        fn drop(&mut self) {
+            tracker::dropped("Inspector");
+            println!("Inspector dropped, it still reads {}", self.0.get());
+        }
+    }
+
+    let local = String::from("local");
+    let borrowing = Inspector(Cell::new(&local));
+    let forever: Inspector<'static> = Inspector(Cell::new("static"));
+    borrowing.0.set("static too");
+    // uncomment the following line to see the error (or read tests/ui/variance2.stderr)
+    // let both = [borrowing, forever];
+}
@@ -0,0 +1,203 @@
+// Show the code of an example with what it did at runtime right under the line that did it: the
+// constructions and drops seen by the tracker, and whatever the example printed. Each line that
+// did something gets a color of its own, shared by what it did, so the two can be told apart at a
+// glance where the plain output would be a second stream to match up by hand.
+//
+// The example runs in a child process that watches the tracker. On every event it captures a
+// backtrace and looks for the frame of the example, whose line is the one that caused the event:
+// the end of a scope or a `drop(x)` for a drop, the call to a constructor for a construction. The
+// functions and closures declared in the example count as the example, so that the arguments of
+// a call are dropped at the end of the callee, where they are.
+// That needs the debug info of a debug build. The child prints a marker to stdout right away, so
+// the markers and the output come back in the order they happened.
+//
+// A printed line goes to the `println!` of the example whose format string matches the most of it,
+// e.g. `"{} dropped"` over `"{}"`, and of those to the first one from the line of the last event
+// on, since that is where the example goes on. If that is in a Drop impl, or there is none, the
+// line goes to the drop that was recorded last, since the types of util.rs record a drop, then
+// print it. Anything else goes to wherever the last event happened.
+
+use dm::{tracker, Example};
+use std::{
+    backtrace::Backtrace,
+    cmp::Reverse,
+    io::{self, IsTerminal},
+    process::Command,
+    sync::OnceLock,
+};
+
+// Set in the child process, to the path of the example function, e.g. `dm::drop_glue::drop_glue3`
+const VIEW: &str = "DM_VIEW";
+const MARKER: char = '\u{1e}';
+const COLORS: [u8; 6] = [31, 32, 33, 34, 35, 36];
+
+static FUNCTION: OnceLock<String> = OnceLock::new();
+
+// The line of the innermost frame of the example function, the closures and functions declared in
+// it included, e.g. `{{closure}}` or `{closure#0}` depending on the toolchain
+fn caller_line() -> Option<usize> {
+    let function = FUNCTION.get()?;
+    let inner = format!("{}::", function);
+    let backtrace = Backtrace::force_capture().to_string();
+    let mut lines = backtrace.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let Some((_, frame)) = line.split_once(": ") else {
+            continue;
+        };
+        if frame == function || frame.starts_with(&inner) {
+            let location = lines.next()?.strip_prefix("at ")?;
+            let mut parts = location.rsplitn(3, ':');
+            let (_, line) = (parts.next()?, parts.next()?);
+            return line.parse().ok();
+        }
+    }
+    None
+}
+
+fn mark(event: &tracker::Event) {
+    let kind = match event.kind {
+        tracker::Kind::Constructed => "constructed",
+        tracker::Kind::Dropped => "dropped",
+    };
+    let line = caller_line().map_or_else(String::new, |line| line.to_string());
+    println!("{}{} {} {}", MARKER, line, kind, event.name);
+}
+
+// Called by the child before it runs the example
+pub fn watch() {
+    if let Ok(function) = std::env::var(VIEW) {
+        let _ = FUNCTION.set(function);
+        tracker::watch(mark);
+    }
+}
+
+// The literal parts of the format string of a `println!` starting at `lines[0]`
+fn format_literals(lines: &[&str]) -> Option<Vec<String>> {
+    if !lines.first()?.contains("print") {
+        return None;
+    }
+    let text = lines.iter().take(3).copied().collect::<Vec<_>>().join(" ");
+    let (_, rest) = text
+        .split_once("print!(")
+        .or(text.split_once("println!("))?;
+    let (_, rest) = rest.split_once('"')?;
+    let end = rest.find('"')?;
+    let literals = rest[..end]
+        .split(['{', '}'])
+        .step_by(2)
+        .filter(|literal| !literal.is_empty())
+        .map(str::to_owned)
+        .collect();
+    Some(literals)
+}
+
+fn matches(literals: &[String], output: &str) -> bool {
+    let mut rest = output;
+    for literal in literals {
+        match rest.find(literal.as_str()) {
+            Some(at) => rest = &rest[at + literal.len()..],
+            None => return false,
+        }
+    }
+    !literals.is_empty()
+}
+
+enum Item {
+    Event(String),
+    Output(String),
+}
+
+pub fn run(example: &Example) -> io::Result<()> {
+    let (_, code) = example.lesson();
+    let signature = format!("pub fn {}() {{", example.name);
+    let first = example
+        .source()
+        .lines()
+        .position(|line| line == signature)
+        .map_or(1, |i| i + 1);
+    let code: Vec<_> = code.lines().collect();
+    let in_code = |line: usize| (first..first + code.len()).contains(&line);
+    // Whether a line of the code is in an impl block declared in the example
+    let mut in_impl = Vec::new();
+    let mut impl_indent = None;
+    for line in &code {
+        let indent = line.len() - line.trim_start().len();
+        if impl_indent.is_none() && line.trim_start().starts_with("impl") {
+            impl_indent = Some(indent);
+        }
+        in_impl.push(impl_indent.is_some());
+        if impl_indent == Some(indent) && line.trim() == "}" {
+            impl_indent = None;
+        }
+    }
+
+    let function = format!("dm::{}::{}", example.topic, example.name);
+    let output = Command::new(std::env::current_exe()?)
+        .arg(example.name)
+        .env(VIEW, function)
+        .output()?;
+
+    // The items under each line of the code, in the order they happened
+    let mut items: Vec<Vec<(usize, Item)>> = code.iter().map(|_| Vec::new()).collect();
+    let (mut last, mut dropping, mut order) = (first, None, 0);
+    for text in String::from_utf8_lossy(&output.stdout).lines() {
+        order += 1;
+        if let Some(marker) = text.strip_prefix(MARKER) {
+            let (line, event) = marker.split_once(' ').unwrap_or(("", marker));
+            let line = line.parse().ok().filter(|&line| in_code(line));
+            last = line.unwrap_or(last);
+            dropping = event.starts_with("dropped").then_some(last);
+            items[last - first].push((order, Item::Event(event.to_owned())));
+            continue;
+        }
+        let printed_by = (0..code.len())
+            .filter_map(|i| {
+                let literals = format_literals(&code[i..])?;
+                let matched: usize = literals.iter().map(String::len).sum();
+                matches(&literals, text).then_some((i, matched))
+            })
+            .max_by_key(|&(i, matched)| (matched, first + i >= last, Reverse(i)))
+            .map(|(i, _)| i);
+        // What a Drop prints belongs to where the value was dropped
+        let line = match printed_by {
+            Some(i) if !in_impl[i] => first + i,
+            _ => dropping.or(printed_by.map(|i| first + i)).unwrap_or(last),
+        };
+        items[line - first].push((order, Item::Output(text.to_owned())));
+    }
+
+    let color = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let mut used = 0;
+    for (i, line) in code.iter().enumerate() {
+        let (start, end) = if items[i].is_empty() || !color {
+            (String::new(), "")
+        } else {
+            used += 1;
+            (
+                format!("\x1b[{}m", COLORS[(used - 1) % COLORS.len()]),
+                "\x1b[0m",
+            )
+        };
+        println!("{}{:4}{} | {}", start, first + i, end, line);
+        let indent = line.len() - line.trim_start().len();
+        for (order, item) in &items[i] {
+            match item {
+                Item::Event(event) => {
+                    println!(
+                        "{}     | {:indent$}^ {:2}. {}{}",
+                        start, "", order, event, end
+                    )
+                }
+                Item::Output(text) => {
+                    println!(
+                        "{}     | {:indent$}> {:2}. {}{}",
+                        start, "", order, text, end
+                    )
+                }
+            }
+        }
+    }
+    // The summary of the heap and whatever went wrong
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    Ok(())
+}
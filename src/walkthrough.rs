@@ -0,0 +1,67 @@
+// A guided version of "read the code, then run it and see": print the code of an example, wait
+// until you have written down what you expect it to print, then reveal the actual output one line
+// at a time and finish with the annotation that explains it.
+
+use crate::run_captured;
+use dm::{Example, EXAMPLES};
+use std::io::{self, BufRead, Write};
+
+// Wait for Enter. Fails when stdin is closed, which ends the walkthrough.
+fn pause(input: &mut impl BufRead, prompt: &str) -> io::Result<()> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    if input.read_line(&mut String::new())? == 0 {
+        println!();
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+fn walk(example: &Example, input: &mut impl BufRead) -> io::Result<()> {
+    let (prose, code) = example.lesson();
+    println!("===== {} =====", example.name);
+    println!("{}\n", code);
+    pause(
+        input,
+        "What does it print? Write it down and press Enter to compare. ",
+    )?;
+
+    let output = run_captured(example.name)?;
+    if output.is_empty() {
+        println!("Nothing at all.");
+    }
+    let lines: Vec<_> = output.lines().collect();
+    for (i, line) in lines.iter().enumerate() {
+        if i + 1 < lines.len() {
+            pause(input, &format!("{:3}| {}", i + 1, line))?;
+        } else {
+            println!("{:3}| {}", i + 1, line);
+        }
+    }
+    println!("\n{}\n", prose);
+    Ok(())
+}
+
+// Walk through the given example, or all of them in reading order
+pub fn run(name: Option<&str>) {
+    let examples: Vec<_> = match name {
+        Some(name) => EXAMPLES
+            .iter()
+            .filter(|example| example.name == name)
+            .collect(),
+        None => EXAMPLES.iter().collect(),
+    };
+    if examples.is_empty() {
+        eprintln!(
+            "unknown example `{}`, run with `list` to see them all",
+            name.unwrap_or("")
+        );
+        std::process::exit(2);
+    }
+    let mut input = io::stdin().lock();
+    for example in examples {
+        if walk(example, &mut input).is_err() {
+            break;
+        }
+    }
+}
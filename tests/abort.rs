@@ -0,0 +1,27 @@
+// With panic=abort, the destructors that would run while unwinding are gone from the output
+
+use std::process::Command;
+
+#[test]
+fn abort_skips_the_drops_of_unwinding() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["abort", "unwind1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let diff: Vec<_> = stdout.lines().skip(1).take(8).collect();
+    assert_eq!(
+        diff,
+        [
+            "  normal exit:",
+            "  work done",
+            "  c dropped",
+            "  b dropped",
+            "  a dropped",
+            "  unwinding:",
+            "- b dropped",
+            "- a dropped",
+        ]
+    );
+}
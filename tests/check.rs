@@ -0,0 +1,82 @@
+// The exercises ship broken, and `check` tells once they are fixed
+
+use std::{env, fs, process::Command};
+
+fn check(exercises: &str, args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .arg("check")
+        .args(args)
+        .env("DM_EXERCISES", exercises)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    (output.status.success(), stdout)
+}
+
+#[test]
+fn no_exercise_is_done_out_of_the_box() {
+    let (done, stdout) = check(concat!(env!("CARGO_MANIFEST_DIR"), "/exercises"), &[]);
+    assert!(!done, "{}", stdout);
+    assert!(stdout.contains("✗ drop_order"), "{}", stdout);
+    assert!(stdout.contains("0 of 3 exercises done"), "{}", stdout);
+    assert!(
+        stdout.contains("drop_order is not done, the tests fail"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn a_fixed_exercise_is_done() {
+    let dir = env::temp_dir().join(format!("dm-exercises-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let broken = include_str!("../exercises/drop_order.rs");
+    let field = "    pub connection: Connection,\n";
+    let fixed = broken.replacen(field, "", 1).replacen(
+        "    pub transaction: Transaction,\n",
+        &format!("    pub transaction: Transaction,\n{}", field),
+        1,
+    );
+    fs::write(dir.join("drop_order.rs"), fixed).unwrap();
+
+    let (done, stdout) = check(dir.to_str().unwrap(), &["drop_order"]);
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(done, "{}", stdout);
+    assert_eq!(stdout, "✓ drop_order\n1 of 1 exercises done\n");
+}
+
+// may_dangle and phantom_data need a nightly rustc
+#[cfg(feature = "nightly")]
+#[test]
+fn every_solution_is_done() {
+    let solutions = concat!(env!("CARGO_MANIFEST_DIR"), "/exercises/solutions");
+    let (done, stdout) = check(solutions, &[]);
+    assert!(done, "{}", stdout);
+    assert!(stdout.ends_with("3 of 3 exercises done\n"), "{}", stdout);
+}
+
+// Whether there is code that must not compile is up to the shipped solution, not to the attempt
+#[cfg(feature = "nightly")]
+#[test]
+fn the_code_that_must_not_compile_is_refused_for_the_right_reason() {
+    let dir = env::temp_dir().join(format!("dm-exercises-refused-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let solution = include_str!("../exercises/solutions/phantom_data.rs");
+    let attempt = |source: String| {
+        fs::write(dir.join("phantom_data.rs"), source).unwrap();
+        check(dir.to_str().unwrap(), &["phantom_data"])
+    };
+
+    let start = solution.find("#[cfg(must_not_compile)]").unwrap();
+    let end = start + solution[start..].find("\n}\n").unwrap() + 3;
+    let deleted = format!("{}{}", &solution[..start], &solution[end..]);
+    let (done, deleted) = attempt(deleted);
+    assert!(!done, "{}", deleted);
+    assert!(deleted.contains("still compiles"), "{}", deleted);
+
+    let typo = solution.replacen("PrintOnDrop(&name)", "PrintOnDrop(&nam)", 1);
+    let (done, typo) = attempt(typo);
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(!done, "{}", typo);
+    assert!(typo.contains("but not with error[E0597]"), "{}", typo);
+}
@@ -0,0 +1,55 @@
+// The completion scripts know the commands, the examples and the exercises
+
+use std::process::Command;
+
+fn script(shell: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["completions", shell])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn every_shell_gets_every_name() {
+    for shell in ["bash", "zsh", "fish"] {
+        let script = script(shell);
+        for name in ["run-all", "drop_glue3", "phantom_data", "intermediate"] {
+            assert!(script.contains(name), "{} has no {}", shell, name);
+        }
+    }
+}
+
+// Only where bash is around, i.e. not on Windows
+#[test]
+fn bash_completes_example_names() {
+    let completions = |line: &str| {
+        let test = format!(
+            "{}\nCOMP_WORDS=({}); COMP_CWORD=$((${{#COMP_WORDS[@]}} - 1)); _dm 2>/dev/null; \
+             echo \"${{COMPREPLY[*]}}\"",
+            script("bash"),
+            line
+        );
+        let output = Command::new("bash").arg("-c").arg(test).output().ok()?;
+        Some(String::from_utf8(output.stdout).unwrap().trim().to_owned())
+    };
+    let Some(drop_glue) = completions("dm drop_glue1") else {
+        return;
+    };
+    assert_eq!(drop_glue, "drop_glue1 drop_glue10");
+    assert_eq!(completions("dm solution may").unwrap(), "may_dangle");
+    assert_eq!(
+        completions("dm list --difficulty = int").unwrap(),
+        "intro intermediate"
+    );
+}
+
+#[test]
+fn knows_only_some_shells() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["completions", "cmd"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,64 @@
+// `#[derive(TraceDrop)]` records the drop of the type itself before the drop glue drops the
+// fields. The tracker log is global, so the tests take turns.
+
+// The fields are only there to be dropped
+#![allow(dead_code)]
+
+use dm::{
+    tracker::{self, Kind},
+    util::DropSpy,
+    TraceDrop,
+};
+use std::sync::Mutex;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn dropped_by(f: impl FnOnce()) -> Vec<&'static str> {
+    let _guard = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    tracker::take();
+    f();
+    let events = tracker::take();
+    assert!(events.iter().all(|event| event.kind == Kind::Dropped));
+    events.into_iter().map(|event| event.name).collect()
+}
+
+#[derive(TraceDrop)]
+struct A(DropSpy, DropSpy);
+
+#[derive(TraceDrop)]
+struct B<T> {
+    first: T,
+    second: DropSpy,
+}
+
+#[derive(TraceDrop)]
+enum C {
+    Spy(DropSpy),
+    Nothing,
+}
+
+#[test]
+fn tuple_struct() {
+    let dropped = dropped_by(|| drop(A(DropSpy("0"), DropSpy("1"))));
+    assert_eq!(dropped, ["A", "0", "1"]);
+}
+
+#[test]
+fn generic_struct() {
+    let dropped = dropped_by(|| {
+        let _b = B {
+            first: A(DropSpy("0"), DropSpy("1")),
+            second: DropSpy("second"),
+        };
+    });
+    assert_eq!(dropped, ["B", "A", "0", "1", "second"]);
+}
+
+#[test]
+fn enum_drops_the_live_variant_only() {
+    let dropped = dropped_by(|| {
+        let _spy = C::Spy(DropSpy("spy"));
+        let _nothing = C::Nothing;
+    });
+    assert_eq!(dropped, ["C", "C", "spy"]);
+}
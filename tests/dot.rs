@@ -0,0 +1,41 @@
+// The ownership graph is read off the struct declarations of an example
+
+use std::process::Command;
+
+fn dot(name: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["dot", name])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn drop_glue_is_recursive() {
+    let expected = r#"digraph drop_glue3 {
+    node [shape=box];
+    "A" [label="A\ndrop glue: 1. A, 2. B1, 3. C1, 4. C2"];
+    "A" -> "B1" [label="owns .0", style=solid];
+    "B1" -> "C1" [label="owns .0", style=solid];
+    "B1" -> "C2" [label="owns .1", style=solid];
+    "C1";
+    "C2";
+}
+"#;
+    assert_eq!(dot("drop_glue3"), expected);
+}
+
+#[test]
+fn references_are_not_owned() {
+    let graph = dot("drop_glue2");
+    assert!(graph.contains(r#""A" -> "B1" [label="borrows .0", style=dashed];"#));
+    assert!(graph.contains(r#"label="A\ndrop glue: 1. A, 2. B2""#));
+}
+
+#[test]
+fn manually_drop_is_skipped_by_the_drop_glue() {
+    let graph = dot("manually_drop1");
+    assert!(graph.contains(r#""A" -> "B" [label="owns (ManuallyDrop) .b", style=bold];"#));
+    assert!(graph.contains(r#"label="A\ndrop glue: 1. A, 2. C""#));
+}
@@ -0,0 +1,19 @@
+// The annotation of an example, rewrapped for the terminal
+
+use std::process::Command;
+
+#[test]
+fn prints_the_prose_wrapped_and_then_the_code() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["explain", "threads3"])
+        .env("COLUMNS", "60")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let text = String::from_utf8(output.stdout).unwrap();
+    let (prose, code) = text.split_once("pub fn threads3() {").unwrap();
+    assert!(prose.starts_with("===== threads3: "));
+    assert!(prose.contains("The main thread is another story"));
+    assert!(prose.lines().skip(1).all(|line| line.chars().count() <= 60));
+    assert!(code.contains("thread_local! {"));
+}
@@ -0,0 +1,68 @@
+// `list` and `run-all` only go through the topics and difficulties asked for
+
+use dm::{Difficulty, EXAMPLES};
+use std::process::Command;
+
+fn dm(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn every_topic_has_a_single_difficulty() {
+    for example in EXAMPLES {
+        let first = EXAMPLES
+            .iter()
+            .find(|other| other.topic == example.topic)
+            .unwrap();
+        assert_eq!(example.difficulty, first.difficulty, "{}", example.name);
+    }
+}
+
+#[test]
+fn list_filters_by_topic_and_difficulty() {
+    let listed = dm(&[
+        "list",
+        "--topic=replace,set_len,drop_order",
+        "--difficulty=intro",
+    ]);
+    let topics: Vec<_> = listed.lines().filter(|line| line.ends_with(':')).collect();
+    assert_eq!(topics, ["drop_order (intro):", "replace (intro):"]);
+
+    let advanced = dm(&["list", "--difficulty=advanced"]);
+    let names: Vec<_> = advanced
+        .lines()
+        .filter_map(|line| line.strip_prefix("  "))
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    let expected: Vec<_> = EXAMPLES
+        .iter()
+        .filter(|example| example.difficulty == Difficulty::Advanced)
+        .map(|example| example.name)
+        .collect();
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn run_all_runs_the_subset_only() {
+    let output = dm(&["run-all", "--topic=replace"]);
+    let banners: Vec<_> = output
+        .lines()
+        .filter(|line| line.starts_with("===== "))
+        .collect();
+    assert_eq!(banners.len(), 3, "{}", output);
+    assert!(banners[0].starts_with("===== replace1: "));
+}
+
+#[test]
+fn unknown_filters_are_refused() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["list", "--difficulty=hard"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
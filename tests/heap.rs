@@ -0,0 +1,31 @@
+// What is left on the heap, counted by the allocator of this test binary. There is a single test,
+// so nothing else allocates while it counts.
+
+use dm::heap::{self, Counting, Usage};
+use std::alloc::System;
+
+#[global_allocator]
+static GLOBAL: Counting<System> = Counting(System);
+
+#[test]
+fn counts_what_is_left() {
+    let before = heap::usage();
+    drop(vec![0u8; 64]);
+    assert_eq!(heap::usage().since(before), Usage::default());
+
+    let before = heap::usage();
+    std::mem::forget(vec![0u8; 64]);
+    Box::leak(Box::new(0u32));
+    let leaked = heap::usage().since(before);
+    assert_eq!(
+        leaked,
+        Usage {
+            bytes: 68,
+            blocks: 2
+        }
+    );
+
+    let before = heap::usage();
+    heap::untracked(|| std::mem::forget(vec![0u8; 64]));
+    assert_eq!(heap::usage().since(before), Usage::default());
+}
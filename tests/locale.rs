@@ -0,0 +1,41 @@
+// The translations in locales/ only cover examples that exist, and replace the English prose
+
+use dm::locale;
+use std::process::Command;
+
+#[test]
+fn every_section_is_a_complete_translation_of_an_example() {
+    for (lang, text) in locale::locales() {
+        let sections = locale::sections(text);
+        assert!(!sections.is_empty(), "{} translates nothing", lang);
+        for (name, summary, prose) in sections {
+            assert!(dm::find(name).is_some(), "{}: no example `{}`", lang, name);
+            assert!(!summary.is_empty(), "{}: `{}` has no summary", lang, name);
+            assert!(!prose.is_empty(), "{}: `{}` has no prose", lang, name);
+        }
+    }
+}
+
+#[test]
+fn lang_picks_the_translation_and_falls_back_to_english() {
+    let explain = |lang: &str, name: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+            .args(["explain", name, &format!("--lang={}", lang)])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let german = explain("de", "drop_glue2");
+    assert!(german.starts_with("===== drop_glue2: Drop-Glue erfasst nur besessene Felder"));
+    assert!(german.contains("wer es besitzt, droppt es."));
+    assert!(german.contains("pub fn drop_glue2() {"));
+    let untranslated = explain("de", "set_len1");
+    assert!(untranslated.contains("`Vec::set_len` changes the length and nothing else"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["list", "--lang=xx"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
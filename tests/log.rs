@@ -0,0 +1,50 @@
+// `-q` keeps only the drops, `-v` and `-vv` tell more on stderr without changing stdout
+
+use std::process::{Command, Output};
+
+fn dm(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    output
+}
+
+#[test]
+fn quiet_prints_only_the_drops() {
+    let stdout = String::from_utf8(dm(&["drop_order2", "-q"]).stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        [
+            "local c dropped",
+            "b dropped",
+            "a dropped",
+            "returned dropped",
+            "local c dropped",
+            "b again dropped",
+            "a again dropped",
+            "returned dropped",
+        ]
+    );
+}
+
+#[test]
+fn verbose_adds_the_events_to_stderr_only() {
+    let normal = dm(&["drop_order2"]);
+    let verbose = dm(&["drop_order2", "-v"]);
+    assert_eq!(normal.stdout, verbose.stdout);
+    let stderr = String::from_utf8(verbose.stderr).unwrap();
+    let dropped = stderr
+        .lines()
+        .find(|line| line.starts_with("[dm] dropped local c, "))
+        .unwrap();
+    assert!(dropped.contains(" bytes at 0x"), "{}", dropped);
+    assert!(!stderr.contains("[dm]   allocated"), "{}", stderr);
+}
+
+#[test]
+fn trace_adds_the_allocations() {
+    let stderr = String::from_utf8(dm(&["leak1", "-vv"]).stderr).unwrap();
+    assert!(stderr.contains("[dm]   allocated 4 bytes\n"), "{}", stderr);
+}
@@ -0,0 +1,54 @@
+// The drops listed by `mir` and `elaborate` come from compiling the example with rustc, with `-Z`
+// flags that only work on nightly
+
+#![cfg(feature = "nightly")]
+
+use std::process::Command;
+
+fn dm(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn drop_order() {
+    let expected = "\
+drops inserted into drop_order() by the compiler:
+  bb0   drop _2 (b): drop_order::B
+  bb1   drop _1 (a): drop_order::A
+  bb3   drop _1 (a): drop_order::A  [unwinding only]
+";
+    assert_eq!(dm(&["mir", "drop_order"]), expected);
+}
+
+#[test]
+fn temporaries_are_dropped_at_the_end_of_the_statement() {
+    let elaborated = dm(&["elaborate", "drop_glue2"]);
+    let expected = "\
+   |     A(&b1, B2());
+     |     ^ drop _2: drop_glue2::A<'_>
+";
+    assert!(elaborated.contains(expected), "{}", elaborated);
+    assert!(elaborated.ends_with("     | ^ drop _1 (b1): drop_glue2::B1 [unwinding only]\n"));
+}
+
+#[test]
+fn drop_flags() {
+    let mir = dm(&["mir", "drop_flags1"]);
+    let maybe: Vec<_> = mir
+        .lines()
+        .filter(|line| line.contains("(maybe)"))
+        .collect();
+    assert_eq!(maybe.len(), 2, "{}", mir);
+    assert!(
+        maybe.iter().all(|line| line.contains("[if drop flag _")),
+        "{}",
+        mir
+    );
+    // The drops without a flag stay as they were
+    assert!(!dm(&["mir", "drop_order"]).contains("drop flag"));
+}
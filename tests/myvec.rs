@@ -0,0 +1,141 @@
+// MyVec drops every element it holds exactly once, and none that it gave away. Run these under
+// Miri as well, see src/myvec.rs.
+
+#![cfg(feature = "nightly")]
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
+
+use dm::{assert_drops, myvec::MyVec, util::DropCounter};
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    cell::Cell,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[test]
+fn push_and_pop() {
+    let mut v = MyVec::new();
+    assert_eq!(v.pop(), None::<i32>);
+    v.push(1);
+    v.push(2);
+    v.push(3);
+    assert_eq!(&*v, [1, 2, 3]);
+    assert_eq!(v.pop(), Some(3));
+    v[0] = 10;
+    assert_eq!(&*v, [10, 2]);
+}
+
+#[test]
+fn grows_and_keeps_the_elements() {
+    let mut v = MyVec::new();
+    for i in 0..100 {
+        v.push(i.to_string());
+    }
+    assert!(v.capacity() >= 100);
+    assert!(v.iter().enumerate().all(|(i, s)| *s == i.to_string()));
+}
+
+#[test]
+fn every_element_is_dropped_once() {
+    let counter = DropCounter::new();
+    let mut v = MyVec::new();
+    for _ in 0..10 {
+        v.push(counter.token());
+    }
+    let popped: Vec<_> = assert_drops!(0, { (0..3).filter_map(|_| v.pop()).collect() });
+    assert_drops!(3, { drop(popped) });
+    assert_drops!(7, { drop(v) });
+    assert_eq!(counter.dropped(), 10);
+}
+
+#[test]
+fn overwritten_elements_are_dropped() {
+    let counter = DropCounter::new();
+    let mut v = MyVec::new();
+    v.push(counter.token());
+    assert_drops!(1, { v[0] = counter.token() });
+    assert_drops!(1, { drop(v) });
+}
+
+#[test]
+fn zero_sized_elements() {
+    // Nowhere to keep a reference to a counter, so the counter is static
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    struct Empty;
+    impl Drop for Empty {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut v = MyVec::new();
+    for _ in 0..5 {
+        v.push(Empty);
+    }
+    assert_eq!(v.len(), 5);
+    assert_eq!(v.capacity(), usize::MAX);
+    drop(v.pop());
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+    drop(v);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 5);
+}
+
+// Compiles only thanks to #[may_dangle]: the references in v dangle when v is dropped, and since
+// a reference has nothing to drop, nothing looks at them
+#[test]
+fn may_dangle() {
+    let mut v;
+    let s = String::from("borrowed");
+    v = MyVec::new();
+    v.push(&s);
+    assert_eq!(v[0], "borrowed");
+}
+
+// The program of the Nomicon's chapter on drop check, with MyVec in place of Vec. The inspectors
+// borrow days, which lives exactly as long as they do, since both are fields of world. Without
+// #[may_dangle] on the Drop of MyVec, `world.days` would not live long enough.
+#[test]
+fn nomicon_inspectors() {
+    struct Inspector<'a>(&'a u8);
+    struct World<'a> {
+        inspectors: MyVec<Inspector<'a>>,
+        days: Box<u8>,
+    }
+
+    let mut world = World {
+        inspectors: MyVec::new(),
+        days: Box::new(1),
+    };
+    world.inspectors.push(Inspector(&world.days));
+    assert_eq!(*world.inspectors[0].0, 1);
+}
+
+// The allocator gets no #[may_dangle]: the elements may dangle when v is dropped, but the counter
+// the allocator borrows may not, since the Drop of MyVec frees the buffer with it
+#[test]
+fn the_allocator_outlives_the_vector() {
+    struct Counting<'c>(&'c Cell<usize>);
+    unsafe impl Allocator for Counting<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    let live = Cell::new(0);
+    {
+        let mut v;
+        let s = String::from("borrowed");
+        v = MyVec::new_in(Counting(&live));
+        for _ in 0..10 {
+            v.push(&s);
+        }
+        assert!(v.iter().all(|borrowed| *borrowed == "borrowed"));
+        assert_eq!(live.get(), 1);
+    }
+    assert_eq!(live.get(), 0);
+}
@@ -0,0 +1,63 @@
+// Every example is also a program on its own, see `Example::program`, which is what `mir`, `miri`,
+// `abort`, `cargo xtask bloat` and the playground of the book compile. A lesson that leans on
+// something only the crate has would break all of them at once.
+
+use std::{env, fs, process::Command, thread};
+
+#[test]
+fn every_example_compiles_on_its_own() {
+    let dir = env::temp_dir().join(format!("dm-program-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let examples: Vec<_> = dm::EXAMPLES
+        .iter()
+        .filter(|example| example.needs().is_none())
+        .collect();
+
+    let failed: Vec<_> = thread::scope(|scope| {
+        let checks: Vec<_> = examples
+            .chunks(8)
+            .map(|chunk| {
+                let (dir, rustc) = (&dir, &rustc);
+                scope.spawn(move || {
+                    let mut failed = Vec::new();
+                    for example in chunk {
+                        let Ok(program) = example.program("") else {
+                            continue;
+                        };
+                        let source = dir.join(format!("{}.rs", example.name));
+                        fs::write(&source, program).unwrap();
+                        let output = Command::new(rustc)
+                            .args(["--edition", "2021", "--crate-type", "bin"])
+                            .args(["--emit=metadata", "--out-dir"])
+                            .arg(dir)
+                            .arg(&source)
+                            .output()
+                            .unwrap();
+                        if !output.status.success() {
+                            let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+                            failed.push(format!("{}:\n{}", example.name, stderr));
+                        }
+                    }
+                    failed
+                })
+            })
+            .collect();
+        checks
+            .into_iter()
+            .flat_map(|check| check.join().unwrap())
+            .collect()
+    });
+    let _ = fs::remove_dir_all(&dir);
+    assert!(failed.is_empty(), "{}", failed.join("\n"));
+}
+
+#[test]
+fn only_temporaries5_is_no_program() {
+    let refused: Vec<_> = dm::EXAMPLES
+        .iter()
+        .filter(|example| example.program("").is_err())
+        .map(|example| example.name)
+        .collect();
+    assert_eq!(refused, ["temporaries5"]);
+}
@@ -0,0 +1,55 @@
+// What the reader has done is saved across runs, shown by `list` and forgotten by `reset`
+
+use std::{
+    env,
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+fn dm(progress: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(args)
+        .env("DM_PROGRESS", progress)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    output
+}
+
+fn listed(progress: &Path, name: &str) -> String {
+    let output = dm(progress, &["list", "--topic=drop_glue"], "");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    stdout
+        .lines()
+        .find(|line| line.split_whitespace().any(|word| word == name))
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn runs_and_quizzes_are_kept_until_reset() {
+    let progress = env::temp_dir().join(format!("dm-progress-{}", std::process::id()));
+    dm(&progress, &["reset"], "");
+    assert!(listed(&progress, "drop_glue3").starts_with("     drop_glue3"));
+
+    dm(&progress, &["drop_glue3"], "");
+    dm(&progress, &["quiz", "drop_glue3"], "A, B1, C1, C2\n");
+    dm(&progress, &["quiz", "drop_glue2"], "wrong\n");
+    assert!(listed(&progress, "drop_glue3").starts_with("  ✓★ drop_glue3"));
+    assert!(listed(&progress, "drop_glue2").starts_with("     drop_glue2"));
+
+    dm(&progress, &["reset"], "");
+    assert!(!progress.exists());
+    assert!(listed(&progress, "drop_glue3").starts_with("     drop_glue3"));
+}
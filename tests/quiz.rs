@@ -0,0 +1,52 @@
+// The answers given count even when the input ends before the quiz does, e.g. piped in
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn quiz(name: &str, input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["quiz", name])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn partial_input_is_graded() {
+    // drop_order has questions of the bank after its own, left unanswered here
+    let output = quiz("drop_order", "b a\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout.contains("Correct!"), "{}", stdout);
+    assert!(stdout.ends_with("1 of 1 predicted right\n"), "{}", stdout);
+}
+
+#[test]
+fn no_input_is_a_failure() {
+    let output = quiz("drop_order", "");
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no answer given"), "{}", stderr);
+}
+
+// The example only tells what it needs, there is no drop to predict
+#[cfg(not(feature = "nightly"))]
+#[test]
+fn an_example_not_built_in_is_not_asked_about() {
+    let output = quiz("may_dangle3", "B A\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("In what order"), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("is not built in"), "{}", stderr);
+}
@@ -0,0 +1,61 @@
+// The question bank under quizzes/ loads, and a broken question is refused with a reason
+
+use dm::quizzes::{self, Question};
+use std::path::Path;
+
+#[test]
+fn the_bank_is_valid() {
+    let questions = quizzes::load(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/quizzes")))
+        .unwrap_or_else(|err| panic!("{}", err));
+    assert!(questions
+        .iter()
+        .any(|question| matches!(question, Question::Order { .. })));
+    assert!(questions
+        .iter()
+        .any(|question| matches!(question, Question::Choice { .. })));
+}
+
+#[test]
+fn broken_questions_are_refused() {
+    let unknown = r#"
+        [[question]]
+        example = "drop_order42"
+        kind = "choice"
+        prompt = "?"
+        choices = ["a", "b"]
+        answer = 1
+    "#;
+    let err = quizzes::parse("unknown.toml", unknown).unwrap_err();
+    assert!(err.contains("no example `drop_order42`"), "{}", err);
+
+    let out_of_range = r#"
+        [[question]]
+        example = "drop_order"
+        kind = "choice"
+        prompt = "?"
+        choices = ["a", "b"]
+        answer = 3
+    "#;
+    let err = quizzes::parse("range.toml", out_of_range).unwrap_err();
+    assert!(err.starts_with("range.toml: question 1:"), "{}", err);
+
+    let missing = r#"
+        [[question]]
+        example = "drop_order"
+        kind = "order"
+        prompt = "?"
+        items = ["a", "b"]
+        answer = ["a", "c"]
+    "#;
+    assert!(quizzes::parse("missing.toml", missing).is_err());
+
+    let typo = r#"
+        [[question]]
+        example = "drop_order"
+        kind = "choice"
+        promt = "?"
+        choices = ["a", "b"]
+        answer = 1
+    "#;
+    assert!(quizzes::parse("typo.toml", typo).is_err());
+}
@@ -0,0 +1,86 @@
+// Pin down what every example prints, so that a refactor or a new compiler version cannot silently
+// change what the lessons demonstrate. Each example runs in its own process of the `dm` binary,
+// exactly like `cargo run -- <name>` would, and what it prints is compared with its snapshot under
+// tests/snapshots/ by insta. A change shows up as a readable diff: run `cargo insta review` to look
+// at it and accept it, or `INSTA_UPDATE=always cargo test --test snapshots` to take new snapshots
+// without cargo-insta. An example without a snapshot fails, and so does a snapshot without an
+// example.
+//
+// What follows one of the prefixes of UNDEFINED is cut off: it is undefined, or differs from one
+// platform to another. The lines of the examples in UNORDERED are sorted, since the order they are
+// printed in is not guaranteed. An example whose topic is behind a feature that is off has no
+// snapshot taken.
+
+use std::{fs, panic, process::Command};
+
+const UNDEFINED: &[&str] = &[
+    "visit a dangling reference: ",
+    "reads freed memory: ",
+    "aborted its process: ",
+    // Which of the thread-locals is dropped first
+    "on thread exit, FIRST is ",
+];
+
+// The thread-locals of a thread are dropped in an order of the platform's choosing
+const UNORDERED: &[&str] = &["threads3"];
+
+fn stdout(name: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .arg(name)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "`dm {}` failed: {:?}",
+        name,
+        output
+    );
+    let mut lines: Vec<_> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            let undefined = UNDEFINED
+                .iter()
+                .find_map(|prefix| Some(line.find(prefix)? + prefix.len()));
+            match undefined {
+                Some(end) => format!("{}[undefined]", &line[..end]),
+                None => line.to_owned(),
+            }
+        })
+        .collect();
+    if UNORDERED.contains(&name) {
+        lines.sort_unstable();
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+// One test for all of them, which still compares every example before it fails, so that a change
+// shows up in full
+#[test]
+fn every_example_prints_its_snapshot() {
+    let mut changed = Vec::new();
+    for example in dm::EXAMPLES {
+        if example.needs().is_some() {
+            continue;
+        }
+        let stdout = stdout(example.name);
+        let result = panic::catch_unwind(|| insta::assert_snapshot!(example.name, stdout));
+        if result.is_err() {
+            changed.push(example.name);
+        }
+    }
+    assert!(changed.is_empty(), "changed: {}", changed.join(", "));
+}
+
+#[test]
+fn every_snapshot_has_its_example() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+    for entry in fs::read_dir(dir).unwrap() {
+        let file = entry.unwrap().file_name().into_string().unwrap();
+        // A `.snap.new` is a snapshot that is yet to be reviewed
+        let Some(name) = file.strip_suffix(".snap") else {
+            continue;
+        };
+        let name = name.strip_prefix("snapshots__").unwrap_or(name);
+        assert!(dm::find(name).is_some(), "{} has no example", file);
+    }
+}
@@ -0,0 +1,37 @@
+// Every exercise comes with a solution, shown as is or as a diff from the attempt
+
+use std::process::Command;
+
+fn solution(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .arg("solution")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn shows_the_solution() {
+    assert_eq!(
+        solution(&["drop_order"]),
+        include_str!("../exercises/solutions/drop_order.rs")
+    );
+}
+
+#[test]
+fn diffs_the_attempt_against_the_solution() {
+    let diff = solution(&["phantom_data", "--diff"]);
+    assert!(diff.starts_with("--- "), "{}", diff);
+    assert!(diff.contains("\n+    _owns: PhantomData<T>,\n"), "{}", diff);
+}
+
+#[test]
+fn knows_only_the_exercises() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["solution", "drop_glue1"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
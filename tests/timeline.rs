@@ -0,0 +1,38 @@
+// The chart of the heap is timed, so only its last line is pinned down
+
+use std::process::Command;
+
+fn verdict(name: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["timeline", name])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    stdout.lines().last().unwrap_or_default().to_owned()
+}
+
+#[test]
+fn nothing_on_the_heap() {
+    assert_eq!(
+        verdict("drop_order"),
+        "0 bytes at most, 0 bytes in 0 blocks still allocated at the end: everything was freed"
+    );
+}
+
+#[test]
+fn freed_at_the_end() {
+    assert!(verdict("phantom1")
+        .ends_with("0 bytes in 0 blocks still allocated at the end: everything was freed"));
+}
+
+#[test]
+fn forgotten_box_is_leaked() {
+    assert!(verdict("forget2")
+        .ends_with("1024 bytes in 1 blocks still allocated at the end: it leaked"));
+}
+
+#[test]
+fn rc_cycle_is_leaked() {
+    assert!(verdict("cycles1").ends_with("it leaked"));
+}
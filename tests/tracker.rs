@@ -0,0 +1,103 @@
+// Check the drop order of the examples through the events they record rather than the lines they
+// print. The log is global, so the tests take turns.
+
+use dm::tracker::{self, Kind};
+use std::sync::Mutex;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn events_of(example: fn()) -> Vec<(Kind, &'static str)> {
+    let _guard = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    tracker::take();
+    example();
+    tracker::take()
+        .into_iter()
+        .map(|event| (event.kind, event.name))
+        .collect()
+}
+
+fn dropped(names: &[&'static str]) -> Vec<(Kind, &'static str)> {
+    names.iter().map(|&name| (Kind::Dropped, name)).collect()
+}
+
+#[test]
+fn drop_order() {
+    assert_eq!(events_of(dm::drop_order::drop_order), dropped(&["B", "A"]));
+}
+
+#[test]
+fn elements_are_dropped_in_index_order() {
+    assert_eq!(
+        events_of(dm::drop_order::drop_order3),
+        dropped(&["tuple.0", "tuple.1", "array[0]", "array[1]", "vec[0]", "vec[1]", "vec[2]"])
+    );
+}
+
+#[test]
+fn drop_glue_runs_after_drop() {
+    assert_eq!(
+        events_of(dm::drop_glue::drop_glue1),
+        dropped(&["A", "B1", "B2"])
+    );
+}
+
+#[test]
+fn drop_glue_skips_references() {
+    assert_eq!(
+        events_of(dm::drop_glue::drop_glue2),
+        dropped(&["A", "B2", "B1"])
+    );
+}
+
+#[test]
+fn drop_glue_is_recursive() {
+    assert_eq!(
+        events_of(dm::drop_glue::drop_glue3),
+        dropped(&["A", "B1", "C1", "C2"])
+    );
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn my_box_is_constructed_before_dropped() {
+    let events = events_of(dm::phantom::phantom1);
+    assert_eq!(
+        events,
+        [(Kind::Constructed, "MyBox"), (Kind::Dropped, "MyBox")]
+    );
+}
+
+#[test]
+fn unwinding_drops_what_exists() {
+    assert_eq!(
+        events_of(dm::unwind::unwind1),
+        dropped(&["c", "b", "a", "b", "a"])
+    );
+}
+
+#[test]
+fn scopes_and_time() {
+    let _guard = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    tracker::take();
+    tracker::dropped("outside");
+    {
+        let _scope = tracker::enter();
+        tracker::dropped("inside");
+    }
+    let events = tracker::take();
+    let depths: Vec<_> = events.iter().map(|event| event.depth).collect();
+    assert_eq!(depths, [0, 1]);
+    assert!(events[0].time <= events[1].time);
+}
+
+#[test]
+fn json() {
+    let _guard = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    tracker::take();
+    tracker::dropped("say \"hi\"");
+    let mut events = tracker::take();
+    events[0].time = std::time::Duration::from_nanos(42);
+    let expected = "[\n  {\"name\": \"say \\\"hi\\\"\", \"kind\": \"dropped\", \"timestamp_ns\": 42, \"depth\": 0}\n]\n";
+    assert_eq!(tracker::to_json(&events), expected);
+    assert_eq!(tracker::to_json(&[]), "[\n]\n");
+}
@@ -0,0 +1,11 @@
+// The lessons that teach by "uncomment these lines and see the error" are kept under `tests/ui`
+// the lines uncommented, together with the error we expect from the compiler. Run
+// `TRYBUILD=overwrite cargo test --test ui` to refresh the `.stderr` files after a compiler update.
+
+#![cfg(feature = "nightly")]
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,24 @@
+// closures3: a closure borrowing a variable declared after it cannot be called by a destructor,
+// since the variable is dropped first.
+
+#![allow(unused)]
+
+struct DropSpy(&'static str);
+impl Drop for DropSpy {
+    fn drop(&mut self) {
+        println!("{} dropped", self.0);
+    }
+}
+
+struct OnDrop<F: FnMut()>(F);
+impl<F: FnMut()> Drop for OnDrop<F> {
+    fn drop(&mut self) {
+        (self.0)();
+    }
+}
+
+fn main() {
+    let early;
+    let late = DropSpy("late");
+    early = OnDrop(|| println!("the guard still sees {}", late.0));
+}
@@ -0,0 +1,27 @@
+// drop_flags3: a field cannot be moved out of a struct that implements Drop, whose `drop` expects
+// every field to be there.
+
+#![allow(unused)]
+
+struct DropSpy(&'static str);
+impl Drop for DropSpy {
+    fn drop(&mut self) {
+        println!("{} dropped", self.0);
+    }
+}
+
+struct Guarded {
+    inner: DropSpy,
+}
+impl Drop for Guarded {
+    fn drop(&mut self) {
+        println!("Guarded still has {}", self.inner.0);
+    }
+}
+
+fn main() {
+    let guarded = Guarded {
+        inner: DropSpy("inner"),
+    };
+    let inner = guarded.inner;
+}
@@ -0,0 +1,9 @@
+// drop_glue10: String has drop glue but no Drop impl of its own, so it doesn't meet `T: Drop`.
+
+#![allow(unused, drop_bounds)]
+
+fn dispose<T: Drop>(value: T) {}
+
+fn main() {
+    dispose(String::from("text"));
+}
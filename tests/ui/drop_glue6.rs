@@ -0,0 +1,19 @@
+// drop_glue6: a type can't be both Copy and Drop, copies of it would all be dropped.
+
+#![allow(unused)]
+
+#[derive(Clone, Copy)]
+struct Point(i32, i32);
+
+#[derive(Clone, Copy)]
+struct Tracked(Point);
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        println!("Tracked({}, {}) dropped", self.0 .0, self.0 .1);
+    }
+}
+
+fn main() {
+    let c = Tracked(Point(1, 2));
+    let d = c;
+}
@@ -0,0 +1,17 @@
+// dropck1: a type with a Drop impl needs its generic parameters to strictly outlive it, even if
+// the Drop doesn't look at them.
+
+#![allow(unused)]
+
+struct Holder<T>(T);
+impl<T> Drop for Holder<T> {
+    fn drop(&mut self) {
+        println!("Holder dropped without looking at its T");
+    }
+}
+
+fn main() {
+    let too_early;
+    let late = String::from("late");
+    too_early = Holder(&late);
+}
@@ -0,0 +1,17 @@
+// dropck2: the type of a closure carries the lifetimes of what it borrows, so the same rule applies
+// to a Holder of a closure.
+
+#![allow(unused)]
+
+struct Holder<T>(T);
+impl<T> Drop for Holder<T> {
+    fn drop(&mut self) {
+        println!("Holder dropped without calling its closure");
+    }
+}
+
+fn main() {
+    let borrowing;
+    let late = String::from("late");
+    borrowing = Holder(|| late.len());
+}
@@ -0,0 +1,16 @@
+// manually_drop4: a union field must be Copy or wrapped in ManuallyDrop, the union can't drop it.
+
+#![allow(unused)]
+
+struct DropSpy(&'static str);
+impl Drop for DropSpy {
+    fn drop(&mut self) {
+        println!("{} dropped", self.0);
+    }
+}
+
+union Wrong {
+    text: DropSpy,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// may_dangle2: an explicit Drop requires the borrowed data to strictly outlive the borrower, even
+// though `drop` never looks at it.
+
+#![allow(unused)]
+
+struct A(i32);
+struct B<'a>(&'a A);
+impl<'a> Drop for B<'a> {
+    fn drop(&mut self) {
+        // even do nothing here
+    }
+}
+
+fn main() {
+    let b;
+    let a = A(42);
+    b = B(&a);
+}
@@ -0,0 +1,26 @@
+// may_dangle3: #[may_dangle] only relaxes the compiler-generated drop. Dropping explicitly is still
+// checked by the borrow checker.
+
+#![allow(unused)]
+#![feature(dropck_eyepatch)]
+
+struct A(i32);
+struct B<'a>(&'a A);
+impl Drop for A {
+    fn drop(&mut self) {
+        println!("A dropped");
+    }
+}
+unsafe impl<#[may_dangle] 'a> Drop for B<'a> {
+    fn drop(&mut self) {
+        println!("B dropped");
+    }
+}
+
+fn main() {
+    let b;
+    let a = A(42);
+    b = B(&a);
+    drop(a);
+    drop(b);
+}
@@ -0,0 +1,22 @@
+// may_dangle6: #[may_dangle] only applies to compiler-generated drop. If you drop variables
+// explicitly, drop checks still remain.
+
+#![allow(unused)]
+#![feature(dropck_eyepatch)]
+
+use std::fmt::Debug;
+
+struct B<T: Debug>(T);
+unsafe impl<#[may_dangle] T: Debug> Drop for B<T> {
+    fn drop(&mut self) {
+        // nothing here
+    }
+}
+
+fn main() {
+    let b;
+    let a = Box::new(42);
+    b = B(&a);
+    drop(a);
+    drop(b);
+}
@@ -0,0 +1,24 @@
+// may_dangle7: #[may_dangle] doesn't make it OK to drop a value that is owned by someone else.
+
+#![allow(unused)]
+#![feature(dropck_eyepatch)]
+
+struct A();
+struct B<T>(T);
+unsafe impl<#[may_dangle] T> Drop for B<T> {
+    fn drop(&mut self) {
+        println!("B dropped");
+    }
+}
+impl Drop for A {
+    fn drop(&mut self) {
+        println!("A dropped as part of the drop glue of B");
+    }
+}
+
+fn main() {
+    let b;
+    let a = A();
+    b = B(a);
+    drop(a);
+}
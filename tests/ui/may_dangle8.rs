@@ -0,0 +1,18 @@
+// may_dangle8: the eyepatch of HashMap doesn't cover a value with a Drop that reads a borrow.
+
+#![allow(unused, clippy::needless_late_init)]
+
+use std::collections::HashMap;
+
+struct PrintOnDrop<'s>(&'s str);
+impl Drop for PrintOnDrop<'_> {
+    fn drop(&mut self) {
+        println!("PrintOnDrop dropped, it still reads {}", self.0);
+    }
+}
+
+fn main() {
+    let late;
+    let t = String::from("t");
+    late = HashMap::from([(1, PrintOnDrop(&t))]);
+}
@@ -0,0 +1,37 @@
+// may_dangle9: neither does the eyepatch of a map of our own.
+
+#![allow(unused, clippy::needless_late_init)]
+#![feature(dropck_eyepatch)]
+
+struct MiniMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+impl<K: PartialEq, V> MiniMap<K, V> {
+    fn new() -> MiniMap<K, V> {
+        MiniMap {
+            entries: Vec::new(),
+        }
+    }
+    fn insert(&mut self, key: K, value: V) {
+        self.entries.retain(|(existing, _)| *existing != key);
+        self.entries.push((key, value));
+    }
+}
+unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for MiniMap<K, V> {
+    fn drop(&mut self) {
+        println!("MiniMap dropped with {} entries", self.entries.len());
+    }
+}
+struct PrintOnDrop<'s>(&'s str);
+impl Drop for PrintOnDrop<'_> {
+    fn drop(&mut self) {
+        println!("PrintOnDrop dropped, it still reads {}", self.0);
+    }
+}
+
+fn main() {
+    let mut late;
+    let t = String::from("t");
+    late = MiniMap::new();
+    late.insert(1, PrintOnDrop(&t));
+}
@@ -0,0 +1,48 @@
+// phantom3: with PhantomData<T>, MyBox owns T in the eyes of the drop checker, so the reference
+// inside PrintOnDrop must outlive the box although the Drop impl says #[may_dangle].
+
+#![allow(unused)]
+#![feature(dropck_eyepatch)]
+
+use std::{
+    alloc::{self, Layout},
+    marker::PhantomData,
+    ptr,
+};
+
+struct MyBox<T>(*mut T, PhantomData<T>);
+struct PrintOnDrop<'s>(&'s str);
+impl<'s> Drop for PrintOnDrop<'s> {
+    fn drop(&mut self) {
+        println!("PrintOnDrop dropped as part of drop glue of MyBox");
+        println!("visit a dangling reference: {}", self.0);
+    }
+}
+unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+    fn drop(&mut self) {
+        println!("MyBox dropped");
+        unsafe {
+            ptr::drop_in_place(self.0);
+            alloc::dealloc(self.0 as *mut u8, Layout::new::<T>())
+        };
+    }
+}
+impl<T> MyBox<T> {
+    fn new() -> MyBox<T> {
+        MyBox(unsafe { alloc::alloc(Layout::new::<T>()) } as *mut T, PhantomData)
+    }
+    fn move_in(&mut self, t: T) {
+        unsafe {
+            std::mem::forget(std::mem::replace(&mut *self.0, t));
+        }
+    }
+}
+
+fn main() {
+    let mut a = MyBox::new();
+    let mut s = "Hello".to_owned();
+    s.push_str(" world");
+    a.move_in(PrintOnDrop(s.as_str()));
+    drop(s);
+    println!("s dropped");
+}
@@ -0,0 +1,11 @@
+// phantom5: a type holding a `*mut T` is invariant in T, so its lifetimes can't be shortened.
+
+#![allow(unused)]
+
+struct RawBox<T>(*mut T);
+
+fn shorten_raw<'a>(b: RawBox<&'static str>) -> RawBox<&'a str> {
+    b
+}
+
+fn main() {}
@@ -0,0 +1,55 @@
+// phantom6: #[may_dangle] is on T only, so the allocator of a box must outlive it.
+
+#![feature(dropck_eyepatch, allocator_api)]
+#![allow(unused, clippy::needless_late_init)]
+
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    cell::Cell,
+    marker::PhantomData,
+    ptr::{self, NonNull},
+};
+
+struct Counting<'c>(&'c Cell<usize>);
+unsafe impl Allocator for Counting<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.set(self.0.get() - 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+struct MyBox<T, A: Allocator = Global> {
+    ptr: NonNull<T>,
+    alloc: A,
+    _owns: PhantomData<T>,
+}
+impl<T, A: Allocator> MyBox<T, A> {
+    fn new_in(value: T, alloc: A) -> MyBox<T, A> {
+        let ptr = alloc.allocate(Layout::new::<T>()).expect("out of memory");
+        let ptr = ptr.cast::<T>();
+        unsafe { ptr.as_ptr().write(value) };
+        MyBox {
+            ptr,
+            alloc,
+            _owns: PhantomData,
+        }
+    }
+}
+unsafe impl<#[may_dangle] T, A: Allocator> Drop for MyBox<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            self.alloc.deallocate(self.ptr.cast(), Layout::new::<T>());
+        }
+    }
+}
+
+fn main() {
+    let late;
+    let late_live = Cell::new(0);
+    late = MyBox::new_in(1, Counting(&late_live));
+}
@@ -0,0 +1,31 @@
+// phantom_data1: a box with PhantomData<T> owns its T, so with #[may_dangle] the drop checker
+// still makes what the T borrows outlive the box.
+
+#![feature(dropck_eyepatch)]
+#![allow(unused)]
+
+use std::{marker::PhantomData, ptr::NonNull};
+
+struct MyBox<T>(NonNull<T>, PhantomData<T>);
+unsafe impl<#[may_dangle] T> Drop for MyBox<T> {
+    fn drop(&mut self) {
+        unsafe { drop(Box::from_raw(self.0.as_ptr())) };
+    }
+}
+impl<T> MyBox<T> {
+    fn new(value: T) -> MyBox<T> {
+        MyBox(NonNull::from(Box::leak(Box::new(value))), PhantomData)
+    }
+}
+struct PrintOnDrop<'s>(&'s str);
+impl Drop for PrintOnDrop<'_> {
+    fn drop(&mut self) {
+        println!("PrintOnDrop dropped, it still reads {}", self.0);
+    }
+}
+
+fn main() {
+    let late;
+    let t = String::from("t");
+    late = MyBox::new(PrintOnDrop(&t));
+}
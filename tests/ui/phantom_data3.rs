@@ -0,0 +1,18 @@
+// phantom_data3: PhantomData<*const T> makes a type neither Send nor Sync.
+
+#![allow(unused)]
+
+use std::marker::PhantomData;
+
+struct Handle<T> {
+    id: u32,
+    _not_send: PhantomData<*const T>,
+}
+
+fn main() {
+    let handle = Handle::<String> {
+        id: 1,
+        _not_send: PhantomData,
+    };
+    std::thread::spawn(move || drop(handle));
+}
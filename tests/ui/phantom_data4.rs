@@ -0,0 +1,16 @@
+// phantom_data4: PhantomData<fn(T) -> T> makes a type invariant in T.
+
+#![allow(unused)]
+
+use std::marker::PhantomData;
+
+struct Marker<T> {
+    id: u32,
+    _invariant: PhantomData<fn(T) -> T>,
+}
+
+fn shorten<'a>(marker: Marker<&'static str>) -> Marker<&'a str> {
+    marker
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// temporaries2: a reference into a temporary cannot be used once the statement that created the
+// temporary is over.
+
+#![allow(unused)]
+
+struct DropSpy(&'static str);
+impl Drop for DropSpy {
+    fn drop(&mut self) {
+        println!("{} dropped", self.0);
+    }
+}
+
+fn first(spies: &[DropSpy; 2]) -> &DropSpy {
+    &spies[0]
+}
+
+fn main() {
+    let x = first(&[DropSpy("x"), DropSpy("y")]);
+    println!("{} borrowed from a temporary", x.0);
+}
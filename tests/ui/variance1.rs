@@ -0,0 +1,18 @@
+// variance1: mixing references shrinks them to the shortest lifetime, which the drop checker
+// then requires to outlive the value.
+
+#![allow(unused)]
+
+struct Inspector<'a>(&'a str);
+impl Drop for Inspector<'_> {
+    fn drop(&mut self) {
+        println!("Inspector dropped, it still reads {}", self.0);
+    }
+}
+
+fn main() {
+    let long = String::from("long");
+    let late;
+    let shorter = String::from("shorter");
+    late = [Inspector(&long), Inspector(&shorter)];
+}
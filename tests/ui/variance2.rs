@@ -0,0 +1,20 @@
+// variance2: an invariant lifetime can't shrink, so an Inspector<'static> can't be put together
+// with one that borrows a local.
+
+#![allow(unused)]
+
+use std::cell::Cell;
+
+struct Inspector<'a>(Cell<&'a str>);
+impl Drop for Inspector<'_> {
+    fn drop(&mut self) {
+        println!("Inspector dropped, it still reads {}", self.0.get());
+    }
+}
+
+fn main() {
+    let local = String::from("local");
+    let borrowing = Inspector(Cell::new(&local));
+    let forever: Inspector<'static> = Inspector(Cell::new("static"));
+    let both = [borrowing, forever];
+}
@@ -0,0 +1,83 @@
+// The shared drop helpers record what they print. The tracker log is global, so the tests take
+// turns.
+
+use dm::{
+    assert_drops,
+    tracker::{self, Kind},
+    util::{DropCounter, DropSpy, NoisyDrop, PanicOnDrop},
+};
+use std::{panic, sync::Mutex};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn events_of(f: impl FnOnce()) -> Vec<(Kind, &'static str)> {
+    let _guard = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    tracker::take();
+    f();
+    let events = tracker::take();
+    events
+        .into_iter()
+        .map(|event| (event.kind, event.name))
+        .collect()
+}
+
+#[test]
+fn drop_spy() {
+    let events = events_of(|| {
+        let _a = DropSpy::new("a");
+        let _b = DropSpy("b");
+    });
+    assert_eq!(
+        events,
+        [
+            (Kind::Constructed, "a"),
+            (Kind::Dropped, "b"),
+            (Kind::Dropped, "a")
+        ]
+    );
+}
+
+#[test]
+fn noisy_drop_drops_its_value_afterwards() {
+    let events = events_of(|| {
+        let mut outer = NoisyDrop::new("outer", vec![DropSpy("inner")]);
+        outer.push(DropSpy("pushed"));
+    });
+    let expected = [
+        (Kind::Constructed, "outer"),
+        (Kind::Dropped, "outer"),
+        (Kind::Dropped, "inner"),
+        (Kind::Dropped, "pushed"),
+    ];
+    assert_eq!(events, expected);
+}
+
+#[test]
+fn panic_on_drop() {
+    let events = events_of(|| {
+        let result = panic::catch_unwind(|| {
+            let _a = DropSpy("a");
+            let _b = PanicOnDrop("b");
+        });
+        assert!(result.is_err());
+    });
+    assert_eq!(events, [(Kind::Dropped, "b"), (Kind::Dropped, "a")]);
+}
+
+#[test]
+fn drop_counter_counts_its_own_tokens() {
+    let (a, b) = (DropCounter::new(), DropCounter::new());
+    let tokens = assert_drops!(1, {
+        let _dropped = a.token();
+        (a.token(), b.token())
+    });
+    assert_drops!(2, { drop(tokens) });
+    assert_eq!((a.dropped(), b.dropped()), (2, 1));
+}
+
+#[test]
+#[should_panic(expected = "expected 1 tokens dropped, got 0")]
+fn assert_drops_panics_on_a_leak() {
+    let counter = DropCounter::new();
+    assert_drops!(1, { std::mem::forget(counter.token()) });
+}
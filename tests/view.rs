@@ -0,0 +1,53 @@
+// The code of an example with what each line did at runtime under it
+
+use std::process::Command;
+
+fn view(name: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_dm"))
+        .args(["view", name])
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn drops_show_up_under_the_line_that_caused_them() {
+    let text = view("drop_glue3");
+    let expected = " 109 |     A(b1);
+     |     ^  1. dropped A
+     |     >  2. Drop for A called
+     |     ^  3. dropped B1
+     |     >  4. Drop for B1 as part of the drop glue of A
+     |     >  5. because the ownership of b1 is transferred to A
+     |     ^  6. dropped C1
+     |     >  7. Drop for C1 as part of the drop glue of B1
+     |     ^  8. dropped C2
+     |     >  9. Drop for C2 as part of the drop glue of B1
+ 110 | }
+";
+    assert!(text.ends_with(expected), "{}", text);
+}
+
+// The arguments are dropped at the end of the callee, and each line is printed by its own statement
+#[test]
+fn every_statement_gets_what_it_did() {
+    let text = view("drop_order2");
+    let expected = "  36 |     }
+     |     ^  2. dropped local c
+     |     >  3. local c dropped
+     |     ^  4. dropped b
+     |     >  5. b dropped
+     |     ^  6. dropped a
+     |     >  7. a dropped
+";
+    assert!(text.contains(expected), "{}", text);
+    let expected = "  39 |     println!(\"back from the call that returned {}\", name);
+     |     > 10. back from the call that returned returned
+  40 |     let kept = call(DropSpy(\"a again\"), DropSpy(\"b again\"));
+  41 |     println!(
+     |     > 18. back from the call that returned returned, kept this time
+";
+    assert!(text.contains(expected), "{}", text);
+}
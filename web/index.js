@@ -0,0 +1,40 @@
+// Fills index.html from the wasm module that `wasm-pack build --target web` puts into pkg/
+import init, * as dm from "./pkg/dm_web.js";
+
+await init();
+
+const $ = (id) => document.getElementById(id);
+let selected = 0;
+
+function select(index) {
+  selected = index;
+  for (const button of document.querySelectorAll("nav button")) {
+    button.classList.toggle("selected", Number(button.dataset.index) === index);
+  }
+  $("name").textContent = dm.name(index);
+  $("description").textContent = dm.description(index);
+  $("code").textContent = dm.code(index);
+  $("prose").textContent = dm.prose(index);
+  $("output").textContent = "";
+}
+
+let topic = "";
+for (let index = 0; index < dm.count(); index++) {
+  if (dm.topic(index) !== topic) {
+    topic = dm.topic(index);
+    const heading = document.createElement("h2");
+    heading.textContent = topic;
+    $("examples").append(heading);
+  }
+  const button = document.createElement("button");
+  button.textContent = dm.name(index);
+  button.dataset.index = index;
+  button.onclick = () => select(index);
+  $("examples").append(button);
+}
+
+$("run").onclick = () => {
+  $("output").textContent = dm.run(selected) || "(nothing printed)";
+};
+
+select(0);
@@ -0,0 +1,84 @@
+// The examples compiled to WebAssembly for the page in index.html, so that the tour can be taken
+// in a browser without installing a toolchain. Build it with `make web`.
+//
+// There is no stdout in a browser. What an example prints is captured the way the test harness
+// does it, which is unstable but so is `#[may_dangle]`: both are behind the `nightly` feature.
+
+#![cfg_attr(feature = "nightly", feature(internal_output_capture))]
+
+use dm::EXAMPLES;
+#[cfg(feature = "nightly")]
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+use wasm_bindgen::prelude::*;
+
+// The examples are looked up by their index in `dm::EXAMPLES`, from 0 to `count() - 1`
+#[wasm_bindgen]
+pub fn count() -> usize {
+    EXAMPLES.len()
+}
+
+#[wasm_bindgen]
+pub fn name(index: usize) -> String {
+    EXAMPLES[index].name.to_owned()
+}
+
+#[wasm_bindgen]
+pub fn topic(index: usize) -> String {
+    EXAMPLES[index].topic.to_owned()
+}
+
+#[wasm_bindgen]
+pub fn description(index: usize) -> String {
+    EXAMPLES[index].summary().to_owned()
+}
+
+#[wasm_bindgen]
+pub fn prose(index: usize) -> String {
+    EXAMPLES[index].lesson().0
+}
+
+#[wasm_bindgen]
+pub fn code(index: usize) -> String {
+    EXAMPLES[index].lesson().1
+}
+
+// Run an example and return what it printed. There is no other process to run the examples that
+// abort in, and aborting would take the whole page down. WebAssembly in a browser can neither
+// unwind a panic nor spawn a thread either, so the examples marked native only have their source
+// on the page.
+#[wasm_bindgen]
+pub fn run(index: usize) -> String {
+    let example = &EXAMPLES[index];
+    if example.aborts {
+        return "This example aborts the process, run it with `cargo run` instead.".to_owned();
+    }
+    if example.native {
+        return format!(
+            "This example panics or spawns a thread, which the browser can't do: run it natively \
+             with `cargo run -- {}`.",
+            example.name
+        );
+    }
+    capture(example.run)
+}
+
+#[cfg(feature = "nightly")]
+fn capture(run: fn()) -> String {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let previous = io::set_output_capture(Some(buffer.clone()));
+    run();
+    io::set_output_capture(previous);
+    let output = buffer
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+#[cfg(not(feature = "nightly"))]
+fn capture(_: fn()) -> String {
+    "Capturing what the example prints needs a nightly toolchain, build the page with `make web`."
+        .to_owned()
+}
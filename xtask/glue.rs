@@ -0,0 +1,46 @@
+// The instantiation patterns `cargo xtask glue` compiles. Every public fn drops its argument, so
+// rustc has to emit the drop glue of each argument type, and of everything these own in turn.
+use std::any::Any;
+
+pub struct Noisy(pub String);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("{} dropped", self.0);
+    }
+}
+
+pub struct Node {
+    pub value: String,
+    pub next: Option<Box<Node>>,
+}
+
+// No glue for the u8 elements, which are Copy, only for the Vec that frees the buffer
+pub fn vec_of_u8(vec: Vec<u8>) {
+    drop(vec)
+}
+
+pub fn vec_of_string(vec: Vec<String>) {
+    drop(vec)
+}
+
+pub fn vec_of_noisy(vec: Vec<Noisy>) {
+    drop(vec)
+}
+
+pub fn array_of_string(array: [String; 4]) {
+    drop(array)
+}
+
+pub fn tuple(tuple: (String, Vec<String>, u64)) {
+    drop(tuple)
+}
+
+// The glue of the concrete type is behind the vtable, only the Box part is generated here
+pub fn boxed_any(boxed: Box<dyn Any>) {
+    drop(boxed)
+}
+
+pub fn list(node: Node) {
+    drop(node)
+}
@@ -0,0 +1,270 @@
+// Chores that are not part of the tour itself. Run them with `cargo xtask <task>`:
+//
+// - book [dir]: generate an mdBook from the annotations and code of the examples into `dir`
+//   (`book` by default). Serve it with `mdbook serve <dir>`.
+// - profile [example]: profile the heap while an example runs, without make. On unix this is what
+//   `make run` does with jemalloc and jeprof, elsewhere (e.g. on Windows) it uses dhat-rs.
+// - glue [opt-level]: compile the patterns of xtask/glue.rs to an object file and list the size
+//   in bytes of every `core::ptr::drop_in_place::<T>` in it, read with nm. Opt-level 0 by default,
+//   at higher levels most of the glue is inlined into the functions that drop.
+// - bloat [example]: the same for the examples, each compiled on its own as a program. Lists the
+//   drop glue of one example, or how much of it every example brings along.
+
+use dm::{Example, EXAMPLES};
+use std::{
+    env,
+    fmt::Write,
+    fs, io,
+    path::Path,
+    process::{Command, ExitStatus},
+};
+
+// Same as RJEM_MALLOC_CONF in the makefile
+const MALLOC_CONF: &str =
+    "prof_leak:true,lg_prof_sample:0,prof_final:true,prof_prefix:./profile/jeprof";
+
+fn title(topic: &str) -> String {
+    let mut title = topic.replace('_', " ");
+    title[..1].make_ascii_uppercase();
+    title
+}
+
+fn book(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(
+        dir.join("book.toml"),
+        "[book]\ntitle = \"Comprehend Drop Check in Rust by Examples\"\n",
+    )?;
+    fs::write(dir.join("src/README.md"), include_str!("../../README.md"))?;
+
+    let mut summary = String::from("# Summary\n\n[Introduction](README.md)\n\n");
+    let mut topics: Vec<&str> = EXAMPLES.iter().map(|example| example.topic).collect();
+    topics.dedup();
+    for topic in topics {
+        let mut examples = EXAMPLES
+            .iter()
+            .filter(|example| example.topic == topic)
+            .peekable();
+        let source = examples.peek().map_or("", |example| example.source());
+        let mut chapter = format!("# {}\n", title(topic));
+        if source.contains("#[may_dangle]") {
+            chapter.push_str("\nThe examples in this chapter need a nightly toolchain.\n");
+        }
+        for example in examples {
+            let (prose, code) = example.lesson();
+            // Whatever is only there to make it compile is hidden behind mdBook's `# ` prefix. An
+            // example that is no program on its own is shown as it is, without a playground.
+            let (attributes, snippet) = match example.program("# ") {
+                Ok(program) => ("rust", program),
+                Err(_) => ("rust,ignore", format!("{}\n", code)),
+            };
+            let _ = write!(
+                chapter,
+                "\n## {}\n\n{}\n\n```{}\n{}```\n",
+                example.name, prose, attributes, snippet
+            );
+        }
+        fs::write(dir.join("src").join(format!("{}.md", topic)), chapter)?;
+        let _ = writeln!(summary, "- [{}]({}.md)", title(topic), topic);
+    }
+    fs::write(dir.join("src/SUMMARY.md"), summary)
+}
+
+fn check(status: ExitStatus) -> io::Result<()> {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("the command failed: {}", status)))
+    }
+}
+
+fn profile(example: &str) -> io::Result<()> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    if !cfg!(unix) {
+        let status = Command::new(cargo)
+            .args(["run", "--features", "dhat", "--", example])
+            .status()?;
+        check(status)?;
+        println!("open dhat-{}.json in the dhat viewer", example);
+        return Ok(());
+    }
+
+    fs::create_dir_all("profile")?;
+    let status = Command::new(cargo)
+        .args(["run", "--", example])
+        .env("_RJEM_MALLOC_CONF", MALLOC_CONF)
+        .status()?;
+    check(status)?;
+    // jemalloc names the profile after the process, pick the one it just wrote
+    let latest = fs::read_dir("profile")?
+        .filter_map(Result::ok)
+        .max_by_key(|entry| entry.metadata().and_then(|meta| meta.modified()).ok())
+        .ok_or_else(|| io::Error::other("jemalloc didn't write any profile"))?;
+    let output = Command::new("jeprof")
+        .args(["--svg", "--show_bytes", "target/debug/dm"])
+        .arg(latest.path())
+        .output()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => io::Error::other(format!(
+                "jeprof not found, it comes with jemalloc. The raw profile is {}",
+                latest.path().display()
+            )),
+            _ => err,
+        })?;
+    check(output.status)?;
+    fs::write("profile.svg", output.stdout)?;
+    println!("wrote profile.svg");
+    Ok(())
+}
+
+// What the drop glue is called, depending on the toolchain and the mangling scheme
+const GLUE: [&str; 3] = [
+    "core::ptr::drop_in_place<",
+    "core::ptr::drop_in_place::<",
+    "core::ptr::drop_glue::<",
+];
+
+// Compile `source` to an object file next to it and read the size in bytes of every drop_in_place
+// instance in it, with the type it drops
+fn drop_glue(source: &Path, crate_type: &str, opt_level: &str) -> io::Result<Vec<(u64, String)>> {
+    let object = source.with_extension("o");
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let output = Command::new(rustc)
+        .args([
+            "--edition",
+            "2021",
+            "--crate-type",
+            crate_type,
+            "--emit=obj",
+        ])
+        .arg(format!("-Copt-level={}", opt_level))
+        .arg("-o")
+        .arg(&object)
+        .arg(source)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("rustc failed:\n{}", stderr)));
+    }
+    let output = Command::new("nm")
+        .args(["--print-size", "--demangle"])
+        .arg(&object)
+        .output()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => io::Error::other(format!(
+                "nm not found, it comes with binutils. The object file is {}",
+                object.display()
+            )),
+            _ => err,
+        })?;
+    check(output.status)?;
+
+    // Each line is `address size type name`, and only the name may contain spaces
+    let mut sizes: Vec<(u64, String)> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, ' ');
+            let (_, size, _, name) = (
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            );
+            let ty = GLUE
+                .iter()
+                .find_map(|prefix| name.strip_prefix(prefix))?
+                .strip_suffix('>')?;
+            Some((u64::from_str_radix(size, 16).ok()?, ty.to_owned()))
+        })
+        .collect();
+    sizes.sort();
+    Ok(sizes)
+}
+
+fn print_sizes(sizes: &[(u64, String)]) {
+    for (size, ty) in sizes {
+        println!("{:>6}  {}", size, ty);
+    }
+    let total: u64 = sizes.iter().map(|(size, _)| size).sum();
+    println!(
+        "{:>6}  bytes in {} drop_in_place instances",
+        total,
+        sizes.len()
+    );
+}
+
+fn glue(opt_level: &str) -> io::Result<()> {
+    let dir = Path::new("target/glue");
+    fs::create_dir_all(dir)?;
+    let source = dir.join("glue.rs");
+    fs::write(&source, include_str!("../glue.rs"))?;
+    print_sizes(&drop_glue(&source, "lib", opt_level)?);
+    Ok(())
+}
+
+// The drop glue of one example in detail, or a line per example with how much glue it brings
+fn bloat(name: Option<&str>) -> io::Result<()> {
+    let dir = Path::new("target/bloat");
+    fs::create_dir_all(dir)?;
+    let compile = |example: &Example| {
+        let source = dir.join(format!("{}.rs", example.name));
+        fs::write(&source, example.program("").map_err(io::Error::other)?)?;
+        drop_glue(&source, "bin", "0")
+    };
+    if let Some(name) = name {
+        let example = dm::find(name)
+            .ok_or_else(|| io::Error::other(format!("unknown example `{}`", name)))?;
+        print_sizes(&compile(example)?);
+        return Ok(());
+    }
+
+    let width = EXAMPLES
+        .iter()
+        .map(|example| example.name.len())
+        .max()
+        .unwrap_or(0);
+    for example in EXAMPLES {
+        match compile(example) {
+            Ok(sizes) => {
+                let total: u64 = sizes.iter().map(|(size, _)| size).sum();
+                println!(
+                    "{:width$}  {:>6} bytes in {:>3} instances",
+                    example.name,
+                    total,
+                    sizes.len()
+                );
+            }
+            // Most likely a nightly example on a stable toolchain
+            Err(err) => {
+                let reason = err.to_string();
+                let reason = reason
+                    .lines()
+                    .find(|line| line.starts_with("error"))
+                    .unwrap_or(&reason);
+                println!("{:width$}  skipped, {}", example.name, reason);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("book") => book(Path::new(args.get(1).map_or("book", String::as_str))),
+        Some("profile") => profile(args.get(1).map_or(EXAMPLES[0].name, String::as_str)),
+        Some("glue") => glue(args.get(1).map_or("0", String::as_str)),
+        Some("bloat") => bloat(args.get(1).map(String::as_str)),
+        _ => {
+            eprintln!("usage: cargo xtask book [dir]");
+            eprintln!("       cargo xtask profile [example]");
+            eprintln!("       cargo xtask glue [opt-level]");
+            eprintln!("       cargo xtask bloat [example]");
+            std::process::exit(2);
+        }
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}